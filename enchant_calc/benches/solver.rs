@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use enchant_calc::{registry::REGISTRY, solver};
+
+// the last one doesn't go on boots, the solver doesn't mind and it makes 10 compatible enchants
+const ENCHANTS: [(&str, u32); 10] = [
+    ("Protection", 4),
+    ("Thorns", 3),
+    ("Unbreaking", 3),
+    ("Mending", 1),
+    ("Curse of Binding", 1),
+    ("Curse of Vanishing", 1),
+    ("Feather Falling", 4),
+    ("Depth Strider", 3),
+    ("Soul Speed", 3),
+    ("Swift Sneak", 3),
+];
+
+fn enchants(count: usize) -> Vec<solver::Enchant> {
+    ENCHANTS[..count]
+        .iter()
+        .map(|(name, level)| solver::Enchant::new(REGISTRY.enchants[*name].clone(), *level))
+        .collect()
+}
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+
+    for count in 6..=ENCHANTS.len() {
+        let enchants = enchants(count);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &enchants,
+            |b, enchants| {
+                b.iter(|| {
+                    solver::Solver::new(enchants).solve(&solver::CancellationToken::new(), |_| {})
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+#[derive(Debug)]
+pub enum RegistryError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+    /// The file extension doesn't match any supported format
+    UnsupportedFormat(String),
+    /// An enchant lists an incompatible enchant that is not in the registry
+    UnknownIncompatible {
+        enchant: String,
+        incompatible: String,
+    },
+    /// An enchant lists an item that doesn't exist
+    UnknownItem {
+        enchant: String,
+        item: String,
+    },
+    /// The same enchant key is given more than once
+    DuplicateKey(String),
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::Io(ref err) => write!(f, "{}", err),
+            RegistryError::Json(ref err) => write!(f, "{}", err),
+            RegistryError::Toml(ref err) => write!(f, "{}", err),
+            RegistryError::UnsupportedFormat(extension) => {
+                write!(f, "Unsupported registry format \"{}\"", extension)
+            }
+            RegistryError::UnknownIncompatible {
+                enchant,
+                incompatible,
+            } => write!(
+                f,
+                "{} is incompatible with unknown enchant {}",
+                enchant, incompatible
+            ),
+            RegistryError::UnknownItem { enchant, item } => {
+                write!(f, "{} applies to unknown item {}", enchant, item)
+            }
+            RegistryError::DuplicateKey(key) => {
+                write!(f, "Enchant {} is given more than once", key)
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for RegistryError {
+    fn from(value: std::io::Error) -> Self {
+        RegistryError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for RegistryError {
+    fn from(value: serde_json::Error) -> Self {
+        RegistryError::Json(value)
+    }
+}
+
+impl From<toml::de::Error> for RegistryError {
+    fn from(value: toml::de::Error) -> Self {
+        RegistryError::Toml(value)
+    }
+}
+
+impl std::error::Error for RegistryError {}
@@ -0,0 +1,1248 @@
+use self::error::RegistryError;
+use lazy_static::lazy_static;
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io::Read,
+    path::Path,
+};
+
+pub mod error;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Registry<'a> {
+    pub enchants: HashMap<Cow<'a, str>, Enchant>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RegistryFormat {
+    Json,
+    Toml,
+}
+
+impl<'a> Registry<'a> {
+    pub fn from_reader(
+        mut reader: impl Read,
+        format: RegistryFormat,
+    ) -> Result<Registry<'static>, RegistryError> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+
+        let mut value: serde_json::Value = match format {
+            RegistryFormat::Json => serde_json::from_str(&data)?,
+            RegistryFormat::Toml => toml::from_str(&data)?,
+        };
+
+        // JSON parsing keeps the last of duplicate keys without telling, TOML already rejects them
+        if format == RegistryFormat::Json {
+            let keys: EnchantKeys = serde_json::from_str(&data)?;
+            let mut seen = HashSet::new();
+            if let Some(key) = keys
+                .enchants
+                .0
+                .into_iter()
+                .find(|key| !seen.insert(key.clone()))
+            {
+                return Err(RegistryError::DuplicateKey(key));
+            }
+        }
+
+        // checking item names first to report them by name instead of as a parse error
+        if let Some(enchants) = value.get("enchants").and_then(|e| e.as_object()) {
+            for (name, enchant) in enchants {
+                let items = enchant.get("items").into_iter().chain(
+                    enchant
+                        .get("bedrock")
+                        .and_then(|bedrock| bedrock.get("items")),
+                );
+
+                for item in items.filter_map(|items| items.as_array()).flatten() {
+                    let Some(item) = item.as_str() else {
+                        continue;
+                    };
+
+                    let parsed: Result<Item, serde::de::value::Error> =
+                        Item::deserialize(item.into_deserializer());
+                    if parsed.is_err() {
+                        return Err(RegistryError::UnknownItem {
+                            enchant: name.clone(),
+                            item: item.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // files from before the item multiplier existed used the book multiplier for both
+        if let Some(enchants) = value.get_mut("enchants").and_then(|e| e.as_object_mut()) {
+            for enchant in enchants.values_mut().filter_map(|e| e.as_object_mut()) {
+                if let Some(weight) = enchant.get("weight").cloned() {
+                    enchant.entry("itemWeight").or_insert(weight);
+                }
+            }
+        }
+
+        let registry: Registry<'static> = serde_json::from_value(value)?;
+
+        // incompatible enchants are referred to by name, like everywhere else
+        let names = registry
+            .enchants
+            .values()
+            .map(|enchant| enchant.name.as_str())
+            .collect::<HashSet<_>>();
+
+        for enchant in registry.enchants.values() {
+            let incompatible = enchant
+                .incompatible
+                .iter()
+                .chain(
+                    enchant
+                        .bedrock
+                        .iter()
+                        .filter_map(|bedrock| bedrock.incompatible.as_ref())
+                        .flatten(),
+                )
+                // empty names are used for enchants without incompatibilities
+                .filter(|e| !e.is_empty());
+
+            for incompatible in incompatible {
+                if !names.contains(incompatible.as_str()) {
+                    return Err(RegistryError::UnknownIncompatible {
+                        enchant: enchant.name.clone(),
+                        incompatible: incompatible.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Loads a registry from a `.json` or `.toml` file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Registry<'static>, RegistryError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let format = match extension.as_str() {
+            "json" => RegistryFormat::Json,
+            "toml" => RegistryFormat::Toml,
+            _ => return Err(RegistryError::UnsupportedFormat(extension)),
+        };
+
+        let file = std::fs::File::open(path)?;
+        Registry::from_reader(std::io::BufReader::new(file), format)
+    }
+
+    /// Creates registries for every edition and version
+    pub fn variants(&self) -> HashMap<(Edition, Version), Registry<'a>> {
+        let mut registries = HashMap::new();
+        for edition in [Edition::Java, Edition::Bedrock] {
+            let edition_registry = self.for_edition(edition);
+            for version in Version::ALL {
+                registries.insert((edition, version), edition_registry.for_version(version));
+            }
+        }
+        registries
+    }
+
+    /// Creates a registry with the edition specific overrides applied
+    pub fn for_edition(&self, edition: Edition) -> Registry<'a> {
+        Registry {
+            enchants: self
+                .enchants
+                .iter()
+                .map(|(name, enchant)| (name.clone(), enchant.for_edition(edition)))
+                .collect(),
+        }
+        .without_unavailable()
+    }
+
+    /// Creates a registry with only the enchants and items available in a version
+    pub fn for_version(&self, version: Version) -> Registry<'a> {
+        Registry {
+            enchants: self
+                .enchants
+                .iter()
+                .filter(|(_, enchant)| enchant.since <= version)
+                .map(|(name, enchant)| {
+                    let mut enchant = enchant.clone();
+                    enchant.items.retain(|item| item.since() <= version);
+                    (name.clone(), enchant)
+                })
+                .collect(),
+        }
+        .without_unavailable()
+    }
+
+    /// Removes enchants that can't be applied to any item and references to enchants that are gone
+    fn without_unavailable(mut self) -> Registry<'a> {
+        self.enchants.retain(|_, enchant| !enchant.items.is_empty());
+
+        let names = self
+            .enchants
+            .values()
+            .map(|enchant| enchant.name.clone())
+            .collect::<HashSet<_>>();
+
+        for enchant in self.enchants.values_mut() {
+            enchant.incompatible.retain(|e| names.contains(e));
+        }
+
+        self
+    }
+
+    /// Checks the registry for inconsistent data
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        let mut keys = self.enchants.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        let mut names = HashMap::new();
+        for key in keys {
+            let enchant = &self.enchants[key];
+
+            if enchant.name != *key {
+                issues.push(ValidationIssue::MismatchedKey {
+                    key: key.to_string(),
+                    name: enchant.name.clone(),
+                });
+            }
+
+            if let Some(other_key) = names.insert(enchant.name.as_str(), key) {
+                issues.push(ValidationIssue::DuplicateName {
+                    name: enchant.name.clone(),
+                    keys: [other_key.to_string(), key.to_string()],
+                });
+            }
+
+            if enchant.items.is_empty() {
+                issues.push(ValidationIssue::NoItems {
+                    enchant: enchant.name.clone(),
+                });
+            }
+
+            for (index, item) in enchant.items.iter().enumerate() {
+                if enchant.items[..index].contains(item) {
+                    issues.push(ValidationIssue::DuplicateItem {
+                        enchant: enchant.name.clone(),
+                        item: *item,
+                    });
+                }
+            }
+
+            for (index, incompatible) in enchant.incompatible.iter().enumerate() {
+                if enchant.incompatible[..index].contains(incompatible) {
+                    issues.push(ValidationIssue::DuplicateIncompatible {
+                        enchant: enchant.name.clone(),
+                        incompatible: incompatible.clone(),
+                    });
+                }
+            }
+
+            if enchant.weight == 0 || enchant.item_weight == 0 {
+                issues.push(ValidationIssue::ZeroWeight {
+                    enchant: enchant.name.clone(),
+                });
+            }
+
+            for incompatible in &enchant.incompatible {
+                let Some(other) = self.enchants.values().find(|e| e.name == *incompatible) else {
+                    issues.push(ValidationIssue::UnknownIncompatible {
+                        enchant: enchant.name.clone(),
+                        incompatible: incompatible.clone(),
+                    });
+                    continue;
+                };
+
+                if !other.incompatible.contains(&enchant.name) {
+                    issues.push(ValidationIssue::AsymmetricIncompatible {
+                        enchant: enchant.name.clone(),
+                        incompatible: incompatible.clone(),
+                    });
+                }
+            }
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+/// Keys of the `enchants` table in file order, including the ones given more than once
+#[derive(Deserialize)]
+struct EnchantKeys {
+    #[serde(default)]
+    enchants: KeyList,
+}
+
+#[derive(Default)]
+struct KeyList(Vec<String>);
+
+impl<'de> Deserialize<'de> for KeyList {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyListVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for KeyListVisitor {
+            type Value = KeyList;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map of enchants")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                    keys.push(key);
+                }
+                Ok(KeyList(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeyListVisitor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// An incompatible enchant is not in the registry, this includes empty names
+    UnknownIncompatible {
+        enchant: String,
+        incompatible: String,
+    },
+    /// An enchant is incompatible with another one, but not the other way around
+    AsymmetricIncompatible {
+        enchant: String,
+        incompatible: String,
+    },
+    /// An enchant can't be applied to any item
+    NoItems { enchant: String },
+    /// Two registry keys point to enchants with the same name
+    DuplicateName { name: String, keys: [String; 2] },
+    /// A registry key differs from the enchant name it points to
+    MismatchedKey { key: String, name: String },
+    /// An enchant has a zero book or item multiplier
+    ZeroWeight { enchant: String },
+    /// An enchant lists the same item more than once
+    DuplicateItem { enchant: String, item: Item },
+    /// An enchant lists the same incompatible enchant more than once
+    DuplicateIncompatible {
+        enchant: String,
+        incompatible: String,
+    },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::UnknownIncompatible {
+                enchant,
+                incompatible,
+            } => write!(
+                f,
+                "{} is incompatible with unknown enchant \"{}\"",
+                enchant, incompatible
+            ),
+            ValidationIssue::AsymmetricIncompatible {
+                enchant,
+                incompatible,
+            } => write!(
+                f,
+                "{} is incompatible with {}, but not the other way around",
+                enchant, incompatible
+            ),
+            ValidationIssue::NoItems { enchant } => {
+                write!(f, "{} can't be applied to any item", enchant)
+            }
+            ValidationIssue::DuplicateName { name, keys } => {
+                write!(f, "{} is used by both {} and {}", name, keys[0], keys[1])
+            }
+            ValidationIssue::MismatchedKey { key, name } => {
+                write!(f, "Key {} points to enchant {}", key, name)
+            }
+            ValidationIssue::ZeroWeight { enchant } => {
+                write!(f, "{} has a zero cost multiplier", enchant)
+            }
+            ValidationIssue::DuplicateItem { enchant, item } => {
+                write!(f, "{} lists {} more than once", enchant, item)
+            }
+            ValidationIssue::DuplicateIncompatible {
+                enchant,
+                incompatible,
+            } => write!(
+                f,
+                "{} lists incompatible enchant {} more than once",
+                enchant, incompatible
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(
+    Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Version {
+    #[default]
+    #[serde(rename = "1.14")]
+    V1_14,
+    #[serde(rename = "1.16")]
+    V1_16,
+    #[serde(rename = "1.19")]
+    V1_19,
+    #[serde(rename = "1.21")]
+    V1_21,
+}
+
+impl Version {
+    pub const LATEST: Version = Version::V1_21;
+    pub const ALL: [Version; 4] = [
+        Version::V1_14,
+        Version::V1_16,
+        Version::V1_19,
+        Version::V1_21,
+    ];
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Version::V1_14 => write!(f, "1.14"),
+            Version::V1_16 => write!(f, "1.16"),
+            Version::V1_19 => write!(f, "1.19"),
+            Version::V1_21 => write!(f, "1.21"),
+        }
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Edition {
+    #[default]
+    Java,
+    Bedrock,
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edition::Java => write!(f, "Java Edition"),
+            Edition::Bedrock => write!(f, "Bedrock Edition"),
+        }
+    }
+}
+
+/// Values that differ from java edition, unset values are the same
+///
+/// Both editions use the same cost multipliers, the overrides mostly change where an enchant
+/// can be applied. Differences that don't affect the anvil, like Impaling hurting every mob in
+/// water on Bedrock, are not part of the registry.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnchantOverride {
+    pub weight: Option<u32>,
+    #[serde(rename = "itemWeight")]
+    pub item_weight: Option<u32>,
+    pub incompatible: Option<Vec<String>>,
+    pub items: Option<Vec<Item>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enchant {
+    pub name: String,
+    #[serde(rename = "levelMax")]
+    pub level_max: u32,
+    /// Cost multiplier when the enchant is on a book sacrifice
+    pub weight: u32,
+    /// Cost multiplier when the enchant is on an item sacrifice, `Registry::from_reader` falls
+    /// back to `weight` for files without it
+    #[serde(rename = "itemWeight")]
+    pub item_weight: u32,
+    pub incompatible: Vec<String>,
+    pub items: Vec<Item>,
+    /// First version the enchant is available in
+    #[serde(default)]
+    pub since: Version,
+    #[serde(default)]
+    pub bedrock: Option<EnchantOverride>,
+}
+
+impl Enchant {
+    pub fn levels_required(&self, enchant_level: u32) -> u32 {
+        enchant_level * self.weight
+    }
+
+    pub fn item_levels_required(&self, enchant_level: u32) -> u32 {
+        enchant_level * self.item_weight
+    }
+
+    pub fn for_edition(&self, edition: Edition) -> Enchant {
+        let mut enchant = self.clone();
+        enchant.bedrock = None;
+
+        let overrides = match edition {
+            Edition::Java => return enchant.with_edition_items(edition),
+            Edition::Bedrock => match self.bedrock {
+                Some(ref overrides) => overrides.clone(),
+                None => return enchant.with_edition_items(edition),
+            },
+        };
+
+        if let Some(weight) = overrides.weight {
+            enchant.weight = weight;
+        }
+        if let Some(item_weight) = overrides.item_weight {
+            enchant.item_weight = item_weight;
+        }
+        if let Some(incompatible) = overrides.incompatible {
+            enchant.incompatible = incompatible;
+        }
+        if let Some(items) = overrides.items {
+            enchant.items = items;
+        }
+
+        enchant.with_edition_items(edition)
+    }
+
+    /// Resolves `Item::ShearsBedrock`, which older registry files use for shears on Bedrock only
+    fn with_edition_items(mut self, edition: Edition) -> Enchant {
+        let shears = self.items.contains(&Item::ShearsBedrock);
+        self.items.retain(|item| *item != Item::ShearsBedrock);
+
+        if shears && edition == Edition::Bedrock && !self.items.contains(&Item::Shears) {
+            self.items.push(Item::Shears);
+        }
+
+        self
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Item {
+    #[default]
+    None,
+    Helmet,
+    Chestplate,
+    Leggings,
+    Boots,
+    TurtleShell,
+    Elytra,
+    Sword,
+    Axe,
+    Trident,
+    Pickaxe,
+    Shovel,
+    Hoe,
+    Bow,
+    Shield,
+    Crossbow,
+    FishingRod,
+    Shears,
+    /// Shears in older registry files for enchants only Bedrock allows on them,
+    /// edition registries turn it into `Item::Shears`
+    ShearsBedrock,
+    FlintAndSteel,
+    Compass,
+    CarrotOnAStick,
+    Pumpkin,
+    WarpedFungusOnAStick,
+    Brush,
+    Mace,
+}
+
+impl Item {
+    /// First version the item can be enchanted in
+    pub fn since(&self) -> Version {
+        match self {
+            Item::WarpedFungusOnAStick => Version::V1_16,
+            Item::Brush | Item::Mace => Version::V1_21,
+            _ => Version::V1_14,
+        }
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Item::None => write!(f, "None"),
+            Item::Helmet => write!(f, "Helmet"),
+            Item::Chestplate => write!(f, "Chestplate"),
+            Item::Leggings => write!(f, "Leggings"),
+            Item::Boots => write!(f, "Boots"),
+            Item::TurtleShell => write!(f, "Turtle Shell"),
+            Item::Elytra => write!(f, "Elytra"),
+            Item::Sword => write!(f, "Sword"),
+            Item::Axe => write!(f, "Axe"),
+            Item::Trident => write!(f, "Trident"),
+            Item::Pickaxe => write!(f, "Pickaxe"),
+            Item::Shovel => write!(f, "Shovel"),
+            Item::Hoe => write!(f, "Hoe"),
+            Item::Bow => write!(f, "Bow"),
+            Item::Shield => write!(f, "Shield"),
+            Item::Crossbow => write!(f, "Crossbow"),
+            Item::FishingRod => write!(f, "Fishing Rod"),
+            Item::Shears => write!(f, "Shears"),
+            Item::ShearsBedrock => write!(f, "Shears (Bedrock Edition)"),
+            Item::FlintAndSteel => write!(f, "Flint And Steel"),
+            Item::Compass => write!(f, "Compass"),
+            Item::CarrotOnAStick => write!(f, "Carrot On A Stick"),
+            Item::Pumpkin => write!(f, "Pumpkin"),
+            Item::WarpedFungusOnAStick => write!(f, "Warped Fungus On A Stick"),
+            Item::Brush => write!(f, "Brush"),
+            Item::Mace => write!(f, "Mace"),
+        }
+    }
+}
+
+const REGISTRY_JSON: &str = r#"
+{
+    "enchants": {
+      "Projectile Protection": {
+        "name": "Projectile Protection",
+        "levelMax": 4,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [
+          "Protection",
+          "Blast Protection",
+          "Fire Protection"
+        ],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "turtle_shell"
+        ]
+      },
+      "Fortune": {
+        "name": "Fortune",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Silk Touch"
+        ],
+        "items": [
+          "pickaxe",
+          "shovel",
+          "axe",
+          "hoe"
+        ]
+      },
+      "Curse of Binding": {
+        "name": "Curse of Binding",
+        "levelMax": 1,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "elytra",
+          "pumpkin",
+          "turtle_shell"
+        ]
+      },
+      "Knockback": {
+        "name": "Knockback",
+        "levelMax": 2,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [],
+        "items": [
+          "sword"
+        ]
+      },
+      "Blast Protection": {
+        "name": "Blast Protection",
+        "levelMax": 4,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Fire Protection",
+          "Protection",
+          "Projectile Protection"
+        ],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "turtle_shell"
+        ]
+      },
+      "Sharpness": {
+        "name": "Sharpness",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 1,
+        "incompatible": [
+          "Bane of Arthropods",
+          "Smite"
+        ],
+        "items": [
+          "sword",
+          "axe"
+        ]
+      },
+      "Bane of Arthropods": {
+        "name": "Bane of Arthropods",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [
+          "Smite",
+          "Sharpness",
+          "Density",
+          "Breach"
+        ],
+        "items": [
+          "sword",
+          "axe",
+          "mace"
+        ]
+      },
+      "Sweeping Edge": {
+        "name": "Sweeping Edge",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "sword"
+        ],
+        "bedrock": {
+          "items": []
+        }
+      },
+      "Power": {
+        "name": "Power",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 1,
+        "incompatible": [],
+        "items": [
+          "bow"
+        ]
+      },
+      "Looting": {
+        "name": "Looting",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "sword"
+        ]
+      },
+      "Aqua Affinity": {
+        "name": "Aqua Affinity",
+        "levelMax": 1,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "helmet",
+          "turtle_shell"
+        ]
+      },
+      "Feather Falling": {
+        "name": "Feather Falling",
+        "levelMax": 4,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [],
+        "items": [
+          "boots"
+        ]
+      },
+      "Fire Aspect": {
+        "name": "Fire Aspect",
+        "levelMax": 2,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "sword",
+          "mace"
+        ]
+      },
+      "Fire Protection": {
+        "name": "Fire Protection",
+        "levelMax": 4,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [
+          "Blast Protection",
+          "Protection",
+          "Projectile Protection"
+        ],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "turtle_shell"
+        ]
+      },
+      "Infinity": {
+        "name": "Infinity",
+        "levelMax": 1,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [
+          "Mending"
+        ],
+        "items": [
+          "bow"
+        ]
+      },
+      "Efficiency": {
+        "name": "Efficiency",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 1,
+        "incompatible": [],
+        "items": [
+          "pickaxe",
+          "shovel",
+          "axe",
+          "hoe",
+          "shears"
+        ]
+      },
+      "Channeling": {
+        "name": "Channeling",
+        "levelMax": 1,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [
+          "Riptide",
+          "Loyalty"
+        ],
+        "items": [
+          "trident"
+        ]
+      },
+      "Luck of the Sea": {
+        "name": "Luck of the Sea",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "fishing_rod"
+        ]
+      },
+      "Lure": {
+        "name": "Lure",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "fishing_rod"
+        ]
+      },
+      "Flame": {
+        "name": "Flame",
+        "levelMax": 1,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "bow"
+        ]
+      },
+      "Impaling": {
+        "name": "Impaling",
+        "levelMax": 5,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "trident"
+        ]
+      },
+      "Frost Walker": {
+        "name": "Frost Walker",
+        "levelMax": 2,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Depth Strider"
+        ],
+        "items": [
+          "boots"
+        ]
+      },
+      "Loyalty": {
+        "name": "Loyalty",
+        "levelMax": 3,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [
+          "Riptide",
+          "Channeling"
+        ],
+        "items": [
+          "trident"
+        ]
+      },
+      "Piercing": {
+        "name": "Piercing",
+        "levelMax": 4,
+        "weight": 1,
+        "itemWeight": 1,
+        "incompatible": [
+          "Multishot"
+        ],
+        "items": [
+          "crossbow"
+        ]
+      },
+      "Quick Charge": {
+        "name": "Quick Charge",
+        "levelMax": 3,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [],
+        "items": [
+          "crossbow"
+        ]
+      },
+      "Unbreaking": {
+        "name": "Unbreaking",
+        "levelMax": 3,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "pickaxe",
+          "shovel",
+          "axe",
+          "sword",
+          "hoe",
+          "fishing_rod",
+          "bow",
+          "shears",
+          "flint_and_steel",
+          "compass",
+          "carrot_on_a_stick",
+          "warped_fungus_on_a_stick",
+          "shield",
+          "elytra",
+          "trident",
+          "turtle_shell",
+          "crossbow",
+          "mace",
+          "brush"
+        ]
+      },
+      "Curse of Vanishing": {
+        "name": "Curse of Vanishing",
+        "levelMax": 1,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "pickaxe",
+          "shovel",
+          "axe",
+          "sword",
+          "hoe",
+          "fishing_rod",
+          "bow",
+          "shears",
+          "flint_and_steel",
+          "compass",
+          "carrot_on_a_stick",
+          "warped_fungus_on_a_stick",
+          "shield",
+          "elytra",
+          "pumpkin",
+          "trident",
+          "turtle_shell",
+          "crossbow",
+          "mace",
+          "brush"
+        ]
+      },
+      "Multishot": {
+        "name": "Multishot",
+        "levelMax": 1,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Piercing"
+        ],
+        "items": [
+          "crossbow"
+        ]
+      },
+      "Thorns": {
+        "name": "Thorns",
+        "levelMax": 3,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "turtle_shell"
+        ]
+      },
+      "Depth Strider": {
+        "name": "Depth Strider",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Frost Walker"
+        ],
+        "items": [
+          "boots"
+        ]
+      },
+      "Protection": {
+        "name": "Protection",
+        "levelMax": 4,
+        "weight": 1,
+        "itemWeight": 1,
+        "incompatible": [
+          "Blast Protection",
+          "Fire Protection",
+          "Projectile Protection"
+        ],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "turtle_shell"
+        ]
+      },
+      "Punch": {
+        "name": "Punch",
+        "levelMax": 2,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [],
+        "items": [
+          "bow"
+        ]
+      },
+      "Riptide": {
+        "name": "Riptide",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Channeling",
+          "Loyalty"
+        ],
+        "items": [
+          "trident"
+        ]
+      },
+      "Smite": {
+        "name": "Smite",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 2,
+        "incompatible": [
+          "Bane of Arthropods",
+          "Sharpness",
+          "Density",
+          "Breach"
+        ],
+        "items": [
+          "sword",
+          "axe",
+          "mace"
+        ]
+      },
+      "Mending": {
+        "name": "Mending",
+        "levelMax": 1,
+        "weight": 2,
+        "itemWeight": 4,
+        "incompatible": [
+          "Infinity"
+        ],
+        "items": [
+          "helmet",
+          "chestplate",
+          "leggings",
+          "boots",
+          "pickaxe",
+          "shovel",
+          "axe",
+          "sword",
+          "hoe",
+          "fishing_rod",
+          "bow",
+          "shears",
+          "flint_and_steel",
+          "compass",
+          "carrot_on_a_stick",
+          "warped_fungus_on_a_stick",
+          "shield",
+          "elytra",
+          "trident",
+          "turtle_shell",
+          "crossbow",
+          "mace",
+          "brush"
+        ]
+      },
+      "Silk Touch": {
+        "name": "Silk Touch",
+        "levelMax": 1,
+        "weight": 4,
+        "itemWeight": 8,
+        "incompatible": [
+          "Fortune"
+        ],
+        "items": [
+          "pickaxe",
+          "shovel",
+          "axe",
+          "hoe"
+        ],
+        "bedrock": {
+          "items": [
+            "pickaxe",
+            "shovel",
+            "axe",
+            "hoe",
+            "shears"
+          ]
+        }
+      },
+      "Soul Speed": {
+        "name": "Soul Speed",
+        "levelMax": 3,
+        "weight": 4,
+        "itemWeight": 8,
+        "since": "1.16",
+        "incompatible": [],
+        "items": [
+          "boots"
+        ]
+      },
+      "Swift Sneak": {
+        "name": "Swift Sneak",
+        "levelMax": 3,
+        "weight": 4,
+        "itemWeight": 8,
+        "since": "1.19",
+        "incompatible": [],
+        "items": [
+          "leggings"
+        ]
+      },
+      "Density": {
+        "name": "Density",
+        "levelMax": 5,
+        "weight": 1,
+        "itemWeight": 2,
+        "since": "1.21",
+        "incompatible": [
+          "Breach",
+          "Smite",
+          "Bane of Arthropods"
+        ],
+        "items": [
+          "mace"
+        ]
+      },
+      "Breach": {
+        "name": "Breach",
+        "levelMax": 4,
+        "weight": 2,
+        "itemWeight": 4,
+        "since": "1.21",
+        "incompatible": [
+          "Density",
+          "Smite",
+          "Bane of Arthropods"
+        ],
+        "items": [
+          "mace"
+        ]
+      },
+      "Wind Burst": {
+        "name": "Wind Burst",
+        "levelMax": 3,
+        "weight": 2,
+        "itemWeight": 4,
+        "since": "1.21",
+        "incompatible": [],
+        "items": [
+          "mace"
+        ]
+      }
+    }
+  }
+"#;
+
+fn load_registry() -> Registry<'static> {
+    Registry::from_reader(REGISTRY_JSON.as_bytes(), RegistryFormat::Json).unwrap()
+}
+
+lazy_static! {
+    static ref RAW_REGISTRY: Registry<'static> = load_registry();
+    static ref REGISTRIES: HashMap<(Edition, Version), Registry<'static>> = RAW_REGISTRY.variants();
+    pub static ref REGISTRY: Registry<'static> = registry(Edition::Java, Version::LATEST).clone();
+}
+
+/// Gets the embedded registry before edition and version specific changes are applied
+pub fn builtin_registry() -> &'static Registry<'static> {
+    &RAW_REGISTRY
+}
+
+/// Gets the registry for an edition and version
+pub fn registry(edition: Edition, version: Version) -> &'static Registry<'static> {
+    &REGISTRIES[&(edition, version)]
+}
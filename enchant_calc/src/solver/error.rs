@@ -0,0 +1,90 @@
+use crate::registry::Item;
+
+/// Reason the selected enchants can't end up on the target item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnchantConflict {
+    /// The enchant doesn't apply to the item
+    NotApplicable { enchant: String, item: Item },
+    /// Two enchants can't be on the same item
+    Incompatible {
+        enchant: String,
+        incompatible: String,
+    },
+    /// The level is 0 or above the enchant's max level
+    InvalidLevel {
+        enchant: String,
+        level: u32,
+        level_max: u32,
+    },
+    /// The item has been worked on so often that every anvil use of it is too expensive
+    TooManyAnvilUses { anvil_use_count: u32 },
+    /// A conflict of the inventory book at `index` in `SolverOptions::books`
+    InBook {
+        index: usize,
+        conflict: Box<EnchantConflict>,
+    },
+    /// A conflict of the sacrificed item at `index` in `SolverOptions::sacrifices`
+    InSacrifice {
+        index: usize,
+        conflict: Box<EnchantConflict>,
+    },
+}
+
+impl std::fmt::Display for EnchantConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnchantConflict::NotApplicable { enchant, item } => {
+                write!(f, "{} can't be applied to {}", enchant, item)
+            }
+            EnchantConflict::Incompatible {
+                enchant,
+                incompatible,
+            } => write!(f, "{} is incompatible with {}", enchant, incompatible),
+            EnchantConflict::InvalidLevel {
+                enchant,
+                level,
+                level_max,
+            } => write!(
+                f,
+                "{} can't be level {}, it goes up to {}",
+                enchant, level, level_max
+            ),
+            EnchantConflict::TooManyAnvilUses { anvil_use_count } => write!(
+                f,
+                "Something worked on {} times is too expensive to use in an anvil",
+                anvil_use_count
+            ),
+            EnchantConflict::InBook { index, conflict } => {
+                write!(f, "Book {}: {}", index + 1, conflict)
+            }
+            EnchantConflict::InSacrifice { index, conflict } => {
+                write!(f, "Sacrifice {}: {}", index + 1, conflict)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SolverError {
+    /// Every conflict between the selected enchants and the target item
+    Conflicts(Vec<EnchantConflict>),
+}
+
+impl std::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::Conflicts(conflicts) => {
+                write!(f, "Enchants conflict with the item: ")?;
+                for (index, conflict) in conflicts.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", conflict)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
@@ -0,0 +1,2082 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use instant::Instant;
+
+use parking_lot::{Mutex, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use self::error::{EnchantConflict, SolverError};
+use crate::registry;
+
+pub mod error;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Enchant {
+    pub enchant: registry::Enchant,
+    pub level: u32,
+}
+
+impl Enchant {
+    pub fn new(enchant: registry::Enchant, level: u32) -> Self {
+        Self { enchant, level }
+    }
+}
+
+/// Cost multiplier of an enchant depending on what the sacrifice carrying it is
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct EnchantMultiplier {
+    book: u32,
+    item: u32,
+}
+
+/// Enchant at the level it currently is on a combination
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct EnchantLevel {
+    /// Index into `Solver::registry_enchants`
+    id: u32,
+    level: u32,
+    level_max: u32,
+    multiplier: EnchantMultiplier,
+}
+
+impl EnchantLevel {
+    /// Level after merging in the same enchant, two equal levels upgrade to the next one up to
+    /// the max level, otherwise the higher level is kept
+    fn merged_level(&self, other_level: u32) -> u32 {
+        match self.level == other_level && self.level < self.level_max {
+            true => self.level + 1,
+            false => u32::max(self.level, other_level),
+        }
+    }
+}
+
+/// Which enchants can't be on the same combination, and which ones the finished item needs
+#[derive(Debug, Default)]
+struct EnchantRules {
+    /// Indexed by the `EnchantLevel::id` of both enchants
+    incompatible: Vec<Vec<bool>>,
+    /// Ids of the enchants the finished item needs with the lowest level it needs them at
+    required: Vec<(u32, u32)>,
+}
+
+impl EnchantRules {
+    fn new(registry_enchants: &[registry::Enchant], required: Vec<(u32, u32)>) -> Self {
+        let incompatible = registry_enchants
+            .iter()
+            .map(|enchant| {
+                registry_enchants
+                    .iter()
+                    .map(|other| {
+                        enchant.incompatible.contains(&other.name)
+                            || other.incompatible.contains(&enchant.name)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        EnchantRules {
+            incompatible,
+            required,
+        }
+    }
+
+    fn is_incompatible(&self, id: u32, other_id: u32) -> bool {
+        id != other_id && self.incompatible[id as usize][other_id as usize]
+    }
+
+    /// Checks if some of the required enchants can't be on the item together
+    fn has_conflicts(&self) -> bool {
+        self.required.iter().enumerate().any(|(index, (id, _))| {
+            self.required[index + 1..]
+                .iter()
+                .any(|(other_id, _)| self.is_incompatible(*id, *other_id))
+        })
+    }
+
+    /// Checks that the remaining combinations still carry every required enchant,
+    /// and at the required level once only the finished item is left
+    fn keeps_required(&self, remaining: &[Arc<ItemKeyCombination>]) -> bool {
+        self.required.iter().all(|(id, level)| match remaining {
+            [finished] => finished
+                .enchant(*id)
+                .map(|enchant| enchant.level >= *level)
+                .unwrap_or(false),
+            _ => remaining.iter().any(|e| e.enchant(*id).is_some()),
+        })
+    }
+}
+
+/// Level cost at which a survival anvil shows "Too Expensive!" and refuses the operation
+pub const TOO_EXPENSIVE_COST: u32 = 40;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TargetItem {
+    /// How many times the item has already been worked on in an anvil
+    pub anvil_use_count: u32,
+    /// Enchants the item already has
+    pub enchants: Vec<Enchant>,
+    /// How much durability the item has lost
+    pub damage: u32,
+}
+
+impl TargetItem {
+    /// Checks if the item already has this enchant at the same or a higher level
+    pub fn has_enchant(&self, enchant: &Enchant) -> bool {
+        self.enchants
+            .iter()
+            .any(|e| e.enchant.name == enchant.enchant.name && e.level >= enchant.level)
+    }
+}
+
+/// A book that is already in the inventory, it might carry several enchants or have been merged before
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct InventoryBook {
+    pub enchants: Vec<Enchant>,
+    /// How many times the book has already been worked on in an anvil
+    pub anvil_use_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SolverOptions {
+    /// Steps costing this many levels or more are rejected, `None` disables the limit (creative mode)
+    pub cost_limit: Option<u32>,
+    /// State of the item the enchants are applied to
+    pub item: TargetItem,
+    /// Books that are applied as they are, in addition to fresh single enchant books
+    pub books: Vec<InventoryBook>,
+    /// Items of the same type as the target that are sacrificed into it
+    pub sacrifices: Vec<TargetItem>,
+    /// Max durability of the item type, used to account for repairs when merging damaged items
+    pub max_durability: u32,
+    /// Edition whose anvil rules are used for pricing
+    pub edition: registry::Edition,
+    /// Stops the search after this long and returns the best path found until then
+    pub time_limit: Option<Duration>,
+    pub strategy: SolverStrategy,
+    pub objective: SolverObjective,
+    /// Also collects every path no other path beats on total cost, most expensive step and
+    /// prior work penalty of the finished item
+    pub pareto: bool,
+    /// Name the item gets, renaming costs a level on top of the step it is done in
+    pub rename: Option<String>,
+    /// Units of the item's repair material available, like diamonds or netherite ingots,
+    /// each one repairs a quarter of `max_durability`
+    pub repair_materials: u32,
+}
+
+/// Score paths reaching the same `Path::state` are compared by, see `SolverOptions::state_score`
+type StateScore = (u32, u32, u32);
+
+impl SolverOptions {
+    /// Key paths reaching the same state are compared by
+    fn state_score(&self, path: &Path) -> StateScore {
+        let (primary, secondary) = self.objective.score(path);
+        match self.pareto {
+            // the objective stays part of it, so the best path for it isn't dropped for a
+            // path that is only better on the front
+            true => (path.cost, path.max_cost, primary),
+            false => (primary, secondary, 0),
+        }
+    }
+
+    /// Whether a path with state score `a` ends up at least as good as one with `b`
+    /// when both continue with the same steps
+    fn state_dominates(&self, a: StateScore, b: StateScore) -> bool {
+        match self.pareto {
+            // the final penalty only depends on the state, so the others decide
+            true => a.0 <= b.0 && a.1 <= b.1 && a.2 <= b.2,
+            false => self.objective.dominates((a.0, a.1), (b.0, b.1)),
+        }
+    }
+}
+
+/// What the solver minimizes, ties are broken by total levels
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SolverObjective {
+    /// Sum of the level costs of all steps
+    #[default]
+    Levels,
+    /// Experience points needed, with the levels of every step collected from level 0
+    Experience,
+    /// Level cost of the most expensive step
+    MaxStep,
+    /// Prior work penalty of the finished item, which every later repair or rename pays
+    PriorWork,
+}
+
+impl SolverObjective {
+    /// Key paths are compared by, lower is better
+    fn score(&self, path: &Path) -> (u32, u32) {
+        self.select(
+            path.cost,
+            path.max_cost,
+            path.experience,
+            path.final_penalty(),
+        )
+    }
+
+    fn select(&self, cost: u32, max_cost: u32, experience: u32, final_penalty: u32) -> (u32, u32) {
+        match self {
+            SolverObjective::Levels => (cost, max_cost),
+            SolverObjective::Experience => (experience, cost),
+            SolverObjective::MaxStep => (max_cost, cost),
+            SolverObjective::PriorWork => (final_penalty, cost),
+        }
+    }
+
+    /// Whether a path with score `a` ends up at least as good as one with score `b`
+    /// when both continue with the same steps
+    fn dominates(&self, a: (u32, u32), b: (u32, u32)) -> bool {
+        match self {
+            // paths in the same state have the same target, so the prior work penalty is equal
+            // and continuing with the same steps keeps it that way
+            SolverObjective::Levels | SolverObjective::Experience | SolverObjective::PriorWork => {
+                a <= b
+            }
+            // a later expensive step can hide a difference in the max step, leaving the total to decide
+            SolverObjective::MaxStep => a.0 <= b.0 && a.1 <= b.1,
+        }
+    }
+}
+
+impl std::fmt::Display for SolverObjective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverObjective::Levels => write!(f, "Total levels"),
+            SolverObjective::Experience => write!(f, "Experience points"),
+            SolverObjective::MaxStep => write!(f, "Most expensive step"),
+            SolverObjective::PriorWork => write!(f, "Prior work penalty"),
+        }
+    }
+}
+
+/// Experience points needed to reach `level` from level 0
+pub fn level_experience(level: u32) -> u32 {
+    let level = level as u128;
+    let points = match level {
+        0..=16 => level * level + 6 * level,
+        17..=31 => (5 * level * level + 720 - 81 * level) / 2,
+        _ => (9 * level * level + 4440 - 325 * level) / 2,
+    };
+
+    u32::try_from(points).unwrap_or(u32::MAX)
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SolverStrategy {
+    /// Explores every path that can be cheaper than the best one found, the result is the cheapest path
+    #[default]
+    Exact,
+    /// Builds the most promising paths a step at a time without backtracking, fast even for
+    /// huge selections but it can miss the cheapest path or not find one at all
+    Greedy,
+}
+
+impl std::fmt::Display for SolverStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverStrategy::Exact => write!(f, "Exact"),
+            SolverStrategy::Greedy => write!(f, "Greedy"),
+        }
+    }
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            cost_limit: Some(TOO_EXPENSIVE_COST),
+            item: TargetItem::default(),
+            books: Vec::new(),
+            sacrifices: Vec::new(),
+            max_durability: 0,
+            edition: registry::Edition::Java,
+            time_limit: None,
+            strategy: SolverStrategy::default(),
+            objective: SolverObjective::default(),
+            pareto: false,
+            rename: None,
+            repair_materials: 0,
+        }
+    }
+}
+
+/// Lowest values a complete path continuing a path can reach, each on its own
+#[derive(Debug, Copy, Clone)]
+struct PathBounds {
+    cost: u32,
+    max_cost: u32,
+    experience: u32,
+    final_penalty: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Path {
+    pub cost: u32,
+    pub max_cost: u32,
+    /// Experience points of all steps, each paid from level 0
+    pub experience: u32,
+    pub remaining: Vec<Arc<ItemKeyCombination>>,
+    pub steps: Vec<Step>,
+    /// Units of repair material not used by a repair yet
+    pub repair_materials: u32,
+    /// Hash of `remaining`, paths with the same state have the same possible continuations
+    pub state: u64,
+    /// Which step was picked at every depth, in the order `explode` generates them, ties between
+    /// equally good complete paths go to the lowest one instead of the one found first
+    ///
+    /// Each step takes 8 bits, so it tells paths apart up to 16 steps and 16 combinations
+    pub order: u128,
+}
+
+impl Path {
+    pub fn with_capacity(cost: u32, max_cost: u32, remaining: usize, steps: usize) -> Path {
+        Path {
+            cost,
+            max_cost,
+            experience: 0,
+            state: 0,
+            order: 0,
+            remaining: Vec::with_capacity(remaining),
+            steps: Vec::with_capacity(steps),
+            repair_materials: 0,
+        }
+    }
+
+    /// Adds the rename to the complete path, returns false if every way to do it is too expensive
+    ///
+    /// Folding it into the cheapest step on the target only adds a level to that step,
+    /// renaming on its own also pays the prior work penalty and adds to it
+    pub fn rename(&mut self, options: &SolverOptions) -> bool {
+        let cheapest = self
+            .steps
+            .iter_mut()
+            .filter(|step| step.can_fold_rename(options))
+            .min_by_key(|step| step.cost);
+        if let Some(step) = cheapest {
+            step.renamed = true;
+            step.cost += RENAME_COST;
+
+            self.cost += RENAME_COST;
+            self.max_cost = u32::max(self.max_cost, step.cost);
+            self.experience = self
+                .experience
+                .saturating_add(level_experience(step.cost))
+                .saturating_sub(level_experience(step.cost - RENAME_COST));
+            return true;
+        }
+
+        let Some(target) = self.remaining.iter_mut().find(|e| e.is_target()) else {
+            return false;
+        };
+        let mut cost = RENAME_COST.saturating_add(target.use_penalty());
+        if let Some(limit) = options.cost_limit.filter(|limit| cost >= *limit) {
+            match options.edition {
+                // Java caps an anvil use that only renames just below the limit instead of refusing it
+                registry::Edition::Java => cost = limit.saturating_sub(1),
+                registry::Edition::Bedrock => return false,
+            }
+        }
+
+        let mut renamed = ItemKeyCombination::clone(target);
+        renamed.anvil_use_count += 1;
+        let left = std::mem::replace(target, Arc::new(renamed));
+
+        self.push_step(Step {
+            left,
+            kind: StepKind::Rename,
+            cost,
+            renamed: true,
+        });
+        true
+    }
+
+    /// Repairs the target with materials in an anvil use of its own, returns false if there is
+    /// nothing to repair or it is too expensive
+    ///
+    /// An anvil use takes at most 4 units, more damage needs another repair later on
+    pub fn repair(&mut self, options: &SolverOptions) -> bool {
+        let Some(target) = self.remaining.iter_mut().find(|e| e.is_target()) else {
+            return false;
+        };
+        if target.damage == 0 {
+            return false;
+        }
+
+        let units = target.material_units(options.max_durability, self.repair_materials);
+        let cost = units.saturating_add(target.use_penalty());
+        if units == 0
+            || options
+                .cost_limit
+                .map(|limit| cost >= limit)
+                .unwrap_or(false)
+        {
+            return false;
+        }
+
+        let mut repaired = ItemKeyCombination::clone(target);
+        repaired.damage = repaired
+            .damage
+            .saturating_sub(units * (options.max_durability / 4));
+        repaired.anvil_use_count += 1;
+        let left = std::mem::replace(target, Arc::new(repaired));
+
+        self.repair_materials -= units;
+        self.push_step(Step {
+            left,
+            kind: StepKind::Repair(units),
+            cost,
+            renamed: false,
+        });
+        true
+    }
+
+    /// Does what is left once everything is merged, returns false if the rename is too expensive
+    ///
+    /// The item gets repaired with the materials that are left until it is whole again, they
+    /// run out or a repair gets too expensive, whatever damage is left stays on the item.
+    /// The rename goes last so it can be folded into a repair too
+    pub fn finish(&mut self, options: &SolverOptions) -> bool {
+        let mut repaired = self.clone();
+        let mut repairs = 0;
+        while repaired.repair(options) {
+            repairs += 1;
+        }
+
+        if options.rename.is_none() {
+            *self = repaired;
+            return true;
+        }
+
+        // every repair adds to the penalty a rename on its own pays, fewer of them can make room for it
+        for repairs in (0..=repairs).rev() {
+            let mut path = self.clone();
+            for _ in 0..repairs {
+                path.repair(options);
+            }
+            if path.rename(options) {
+                *self = path;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn push_step(&mut self, step: Step) {
+        self.cost = self.cost.saturating_add(step.cost);
+        self.max_cost = u32::max(self.max_cost, step.cost);
+        self.experience = self.experience.saturating_add(level_experience(step.cost));
+        self.steps.push(step);
+    }
+
+    /// Hashes what decides the possible continuations into `state`
+    ///
+    /// The rename goes into the cheapest step on the target it fits in, so that step decides
+    /// what the rename adds to the score as much as the remaining combinations do
+    fn update_state(&mut self, options: &SolverOptions) {
+        let mut hasher = DefaultHasher::new();
+        self.remaining.hash(&mut hasher);
+        self.repair_materials.hash(&mut hasher);
+        if options.rename.is_some() {
+            self.steps
+                .iter()
+                .filter(|step| step.can_fold_rename(options))
+                .map(|step| step.cost)
+                .min()
+                .hash(&mut hasher);
+        }
+        self.state = hasher.finish();
+    }
+
+    pub fn is_more_effective(&self, other: &Path, objective: SolverObjective) -> bool {
+        (objective.score(self), self.order) < (objective.score(other), other.order)
+    }
+
+    /// Lowest objective score any complete path continuing this one can reach
+    pub fn lower_bound(
+        &self,
+        options: &SolverOptions,
+        rules: &EnchantRules,
+        penalty_bounds: &PenaltyBounds,
+    ) -> (u32, u32) {
+        let bounds = self.bounds(options, rules, penalty_bounds);
+        options.objective.select(
+            bounds.cost,
+            bounds.max_cost,
+            bounds.experience,
+            bounds.final_penalty,
+        )
+    }
+
+    /// Lowest total cost, most expensive step and final prior work penalty any complete path
+    /// continuing this one can reach
+    pub fn pareto_bound(
+        &self,
+        options: &SolverOptions,
+        rules: &EnchantRules,
+        penalty_bounds: &PenaltyBounds,
+    ) -> (u32, u32, u32) {
+        let bounds = self.bounds(options, rules, penalty_bounds);
+        (bounds.cost, bounds.max_cost, bounds.final_penalty)
+    }
+
+    fn bounds(
+        &self,
+        options: &SolverOptions,
+        rules: &EnchantRules,
+        penalty_bounds: &PenaltyBounds,
+    ) -> PathBounds {
+        if self.remaining.len() <= 1 {
+            return PathBounds {
+                cost: self.cost,
+                max_cost: self.max_cost,
+                experience: self.experience,
+                final_penalty: self.final_penalty(),
+            };
+        }
+
+        let steps = self.remaining.len() as u32 - 1;
+        let levels = self.remaining_levels_bound(options.edition, rules, penalty_bounds);
+
+        // experience grows faster than levels, so spreading the levels evenly needs the fewest points
+        let (even, extra) = (levels / steps, levels % steps);
+        let experience = self
+            .experience
+            .saturating_add(extra.saturating_mul(level_experience(even.saturating_add(1))))
+            .saturating_add((steps - extra).saturating_mul(level_experience(even)));
+
+        // everything gets merged at least once more, and merging n combinations takes log2(n) rounds
+        let max_use_count = self
+            .remaining
+            .iter()
+            .map(|e| e.anvil_use_count)
+            .max()
+            .unwrap_or(0);
+        let rounds = (self.remaining.len() as u32)
+            .next_power_of_two()
+            .trailing_zeros();
+        let use_count = u32::max(max_use_count + 1, rounds);
+
+        // renaming adds at least a level to some step
+        let rename = match options.rename {
+            Some(_) => 1,
+            None => 0,
+        };
+
+        PathBounds {
+            cost: self.cost.saturating_add(levels).saturating_add(rename),
+            max_cost: u32::max(self.max_cost, levels.div_ceil(steps)),
+            experience,
+            final_penalty: PenaltyBounds::penalty(use_count),
+        }
+    }
+
+    /// Total cost, most expensive step and final prior work penalty of a complete path
+    pub fn pareto_score(&self) -> (u32, u32, u32) {
+        (self.cost, self.max_cost, self.final_penalty())
+    }
+
+    /// Damage the finished item is left with
+    pub fn final_damage(&self) -> u32 {
+        self.remaining
+            .iter()
+            .find(|e| e.is_target())
+            .map(|e| e.damage)
+            .unwrap_or(0)
+    }
+
+    /// Prior work penalty of the finished item
+    pub fn final_penalty(&self) -> u32 {
+        self.remaining
+            .iter()
+            .find(|e| e.is_target())
+            .map(|e| e.use_penalty())
+            .unwrap_or(0)
+    }
+
+    /// Lowest number of levels the remaining steps cost together
+    fn remaining_levels_bound(
+        &self,
+        edition: registry::Edition,
+        rules: &EnchantRules,
+        penalty_bounds: &PenaltyBounds,
+    ) -> u32 {
+        if self.remaining.len() <= 1 {
+            return 0;
+        }
+
+        let penalties = penalty_bounds.min_penalties(
+            self.remaining
+                .iter()
+                .map(|e| e.anvil_use_count)
+                .collect::<Vec<_>>(),
+        );
+
+        // every enchant that is not on the target gets sacrificed at least once at its current level or higher
+        let enchants = self
+            .remaining
+            .iter()
+            .filter(|e| !e.is_target())
+            .flat_map(|e| e.enchants.iter())
+            .filter(|enchant| match edition {
+                registry::Edition::Java => true,
+                // only gained levels are paid, so only enchants no other combination has are sure to be paid in full
+                registry::Edition::Bedrock => {
+                    self.remaining
+                        .iter()
+                        .filter(|e| e.enchant(enchant.id).is_some())
+                        .count()
+                        == 1
+                }
+            })
+            .map(|enchant| {
+                let cost =
+                    enchant.level * u32::min(enchant.multiplier.book, enchant.multiplier.item);
+
+                // an enchant that meets an incompatible one on the way only pays for being dropped
+                let droppable = self.remaining.iter().any(|e| {
+                    e.enchants
+                        .iter()
+                        .any(|other| rules.is_incompatible(enchant.id, other.id))
+                });
+                match droppable {
+                    true => u32::min(cost, ItemKeyCombination::dropped_cost(edition)),
+                    false => cost,
+                }
+            })
+            .sum::<u32>();
+
+        penalties.saturating_add(enchants)
+    }
+
+    /// Returns every path one step further, how many steps were tried and how many of the
+    /// resulting paths were dropped for reaching the same state as another one
+    pub fn explode(&self, options: &SolverOptions, rules: &EnchantRules) -> (Vec<Path>, u32, u32) {
+        // kept in the order they were generated, which `Path::order` follows
+        let mut best_paths: Vec<Path> = Vec::new();
+        let mut best_path_indices: HashMap<u64, usize> = HashMap::new();
+        let mut tries = 0;
+        let mut duplicates = 0;
+
+        let mut insert = |new_path: Path| {
+            if let Some(&index) = best_path_indices.get(&new_path.state) {
+                let new_score = options.state_score(&new_path);
+                let old_score = options.state_score(&best_paths[index]);
+
+                if options.state_dominates(old_score, new_score) {
+                    duplicates += 1;
+                } else if options.state_dominates(new_score, old_score) {
+                    duplicates += 1;
+                    best_paths[index] = new_path;
+                } else {
+                    // neither is better for every continuation, so both get explored
+                    best_paths.push(new_path);
+                }
+            } else {
+                best_path_indices.insert(new_path.state, best_paths.len());
+                best_paths.push(new_path);
+            }
+        };
+
+        let len = self.remaining.len();
+
+        for left in 0..len {
+            for right in 0..len {
+                if right == left {
+                    continue;
+                }
+
+                // the target item can't be sacrificed, and an item can't be put onto a book
+                if self.remaining[right].is_target()
+                    || (self.remaining[right].is_item() && !self.remaining[left].is_item())
+                {
+                    continue;
+                }
+
+                let mut new_path =
+                    Path::with_capacity(0, 0, self.remaining.capacity(), self.steps.capacity());
+
+                for i in 0..len {
+                    if i != left && i != right {
+                        new_path.remaining.push(self.remaining[i].clone());
+                    }
+                }
+
+                let left_item = &self.remaining[left];
+                let right_item = &self.remaining[right];
+
+                let step_cost_enchants = left_item.cost(right_item, rules, options.edition);
+                let step_cost_penalties = left_item
+                    .use_penalty()
+                    .saturating_add(right_item.use_penalty());
+                let step_cost_repair = left_item.repair_cost(right_item, options.max_durability);
+                let step_cost = step_cost_enchants
+                    .saturating_add(step_cost_penalties)
+                    .saturating_add(step_cost_repair);
+
+                tries += 1;
+
+                // the anvil refuses a sacrifice that neither adds an enchant nor repairs anything
+                let dropped = left_item.dropped(right_item, rules).count();
+                if dropped > 0 && dropped == right_item.enchants.len() && step_cost_repair == 0 {
+                    continue;
+                }
+
+                if options
+                    .cost_limit
+                    .map(|limit| step_cost >= limit)
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+
+                let combined = left_item.combine(right_item, rules, options.max_durability);
+
+                new_path.remaining.push(Arc::new(combined));
+                new_path.remaining.sort();
+
+                // a merge can also leave an enchant below the required level without dropping anything
+                if (dropped > 0 || new_path.remaining.len() == 1)
+                    && !rules.keeps_required(&new_path.remaining)
+                {
+                    continue;
+                }
+
+                new_path.steps.reserve(self.steps.len() + 1);
+                new_path.steps.extend(self.steps.clone());
+                new_path.cost = self.cost;
+                new_path.max_cost = self.max_cost;
+                new_path.experience = self.experience;
+                new_path.repair_materials = self.repair_materials;
+                new_path.order = (self.order << 8) | (left * 16 + right).min(0xFE) as u128;
+                new_path.push_step(Step {
+                    left: left_item.clone(),
+                    kind: StepKind::Combine(right_item.clone()),
+                    cost: step_cost,
+                    renamed: false,
+                });
+
+                if new_path.remaining.len() == 1 && !new_path.finish(options) {
+                    continue;
+                }
+
+                new_path.update_state(options);
+                insert(new_path);
+            }
+        }
+
+        // repairing with materials is an anvil use of its own, so it can go between any steps
+        if len > 1 {
+            let mut new_path = self.clone();
+            if new_path.repair(options) {
+                new_path.order = (self.order << 8) | 0xFF;
+                tries += 1;
+                new_path.update_state(options);
+                insert(new_path);
+            }
+        }
+
+        (best_paths, tries, duplicates)
+    }
+}
+
+/// Memoized lowest total prior work penalty of merging combinations with the given use counts,
+/// ignoring which side each combination has to go on
+#[derive(Debug, Default)]
+struct PenaltyBounds {
+    bounds: RwLock<HashMap<Vec<u32>, u32>>,
+}
+
+impl PenaltyBounds {
+    /// Prior work penalty of something used this many times, `u32::MAX` once it doesn't fit
+    fn penalty(anvil_use_count: u32) -> u32 {
+        2_u32
+            .checked_pow(anvil_use_count)
+            .map_or(u32::MAX, |factor| factor - 1)
+    }
+
+    pub fn min_penalties(&self, mut anvil_use_counts: Vec<u32>) -> u32 {
+        if anvil_use_counts.len() <= 1 {
+            return 0;
+        }
+
+        anvil_use_counts.sort_unstable();
+        if let Some(bound) = self.bounds.read().get(&anvil_use_counts) {
+            return *bound;
+        }
+
+        let mut best = u32::MAX;
+        for left in 0..anvil_use_counts.len() {
+            if left > 0 && anvil_use_counts[left] == anvil_use_counts[left - 1] {
+                continue;
+            }
+
+            for right in left + 1..anvil_use_counts.len() {
+                // equal use counts lead to the same states
+                if right > left + 1 && anvil_use_counts[right] == anvil_use_counts[right - 1] {
+                    continue;
+                }
+
+                let mut next = anvil_use_counts.clone();
+                let right_count = next.remove(right);
+                let left_count = next.remove(left);
+                next.push(u32::max(left_count, right_count) + 1);
+
+                let cost = PenaltyBounds::penalty(left_count)
+                    .saturating_add(PenaltyBounds::penalty(right_count))
+                    .saturating_add(self.min_penalties(next));
+                best = u32::min(best, cost);
+            }
+        }
+
+        self.bounds.write().insert(anvil_use_counts, best);
+        best
+    }
+}
+
+/// Best score every `Path::state` has been reached with, shared by all search branches
+///
+/// Of two equally good paths reaching a state, the one that gets there first is explored,
+/// so when two branches race there which of several equally good results is found can vary
+#[derive(Debug)]
+struct VisitedStates {
+    /// Split by state so parallel workers rarely wait on each other
+    shards: Vec<Mutex<HashMap<u64, StateScore>>>,
+}
+
+impl VisitedStates {
+    const SHARDS: usize = 64;
+
+    /// Records the path's state, returns false if it was already reached by a path that is
+    /// at least as good
+    pub fn visit(&self, path: &Path, options: &SolverOptions) -> bool {
+        let mut shard = self.shards[path.state as usize % VisitedStates::SHARDS].lock();
+        let score = options.state_score(path);
+
+        if let Some(visited_score) = shard.get(&path.state) {
+            if options.state_dominates(*visited_score, score) {
+                return false;
+            }
+        }
+
+        shard.insert(path.state, score);
+        true
+    }
+}
+
+impl Default for VisitedStates {
+    fn default() -> Self {
+        VisitedStates {
+            shards: (0..VisitedStates::SHARDS)
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+        }
+    }
+}
+
+/// Complete paths no other found path beats on every part of `Path::pareto_score`
+#[derive(Debug, Default)]
+struct ParetoFront {
+    paths: Vec<Path>,
+}
+
+impl ParetoFront {
+    fn dominates(a: (u32, u32, u32), b: (u32, u32, u32)) -> bool {
+        a.0 <= b.0 && a.1 <= b.1 && a.2 <= b.2
+    }
+
+    /// Whether a path with this score would be beaten by or equal to one already in the front
+    pub fn covers(&self, score: (u32, u32, u32)) -> bool {
+        self.paths
+            .iter()
+            .any(|path| ParetoFront::dominates(path.pareto_score(), score))
+    }
+
+    pub fn insert(&mut self, path: Path) {
+        let score = path.pareto_score();
+        // of two equal paths the one first in `Path::order` stays, whichever branch finds it first
+        let beaten = self.paths.iter().any(|e| {
+            let e_score = e.pareto_score();
+            ParetoFront::dominates(e_score, score) && (e_score != score || e.order <= path.order)
+        });
+        if beaten {
+            return;
+        }
+
+        self.paths
+            .retain(|e| !ParetoFront::dominates(score, e.pareto_score()));
+        self.paths.push(path);
+    }
+}
+
+/// State of a single `Solver::solve` call shared between all search branches
+struct Search<F> {
+    best_path: Mutex<Option<Path>>,
+    pareto_front: Mutex<ParetoFront>,
+    /// Objective score of `best_path`, kept separately so branches can prune without locking
+    best_score: AtomicU64,
+    visited: VisitedStates,
+    progress_callback: Mutex<F>,
+    cancellation: CancellationToken,
+    deadline: Option<Instant>,
+    timed_out: AtomicBool,
+    /// Number of times `best_path` got replaced, so improvements are reported in that order
+    improvements: AtomicU64,
+    /// Last improvement reported to `progress_callback`
+    reported_improvement: AtomicU64,
+    /// Tried paths not reported to `progress_callback` yet
+    unreported_paths_tried: AtomicU64,
+    paths_tried: AtomicU64,
+    nodes_expanded: AtomicU64,
+    leaves: AtomicU64,
+    pruned: AtomicU64,
+    deduplicated: AtomicU64,
+}
+
+impl<F: FnMut(SolverProgress) + Send> Search<F> {
+    fn new(
+        progress_callback: F,
+        cancellation: CancellationToken,
+        deadline: Option<Instant>,
+    ) -> Self {
+        Search {
+            best_path: Mutex::new(None),
+            pareto_front: Mutex::new(ParetoFront::default()),
+            best_score: AtomicU64::new(u64::MAX),
+            visited: VisitedStates::default(),
+            progress_callback: Mutex::new(progress_callback),
+            cancellation,
+            deadline,
+            timed_out: AtomicBool::new(false),
+            improvements: AtomicU64::new(0),
+            reported_improvement: AtomicU64::new(0),
+            unreported_paths_tried: AtomicU64::new(0),
+            paths_tried: AtomicU64::new(0),
+            nodes_expanded: AtomicU64::new(0),
+            leaves: AtomicU64::new(0),
+            pruned: AtomicU64::new(0),
+            deduplicated: AtomicU64::new(0),
+        }
+    }
+
+    /// Objective score packed into a single number so it can be shared atomically,
+    /// compares the same as the score
+    fn pack_score(primary: u32, secondary: u32) -> u64 {
+        ((primary as u64) << 32) | secondary as u64
+    }
+
+    fn score(path: &Path, options: &SolverOptions) -> u64 {
+        let (primary, secondary) = options.objective.score(path);
+        Search::<F>::pack_score(primary, secondary)
+    }
+
+    /// Whether the search got cancelled or ran out of time
+    fn stopped(&self) -> bool {
+        if self.cancellation.is_cancelled() || self.timed_out.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        if self
+            .deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+        {
+            self.timed_out.store(true, Ordering::Relaxed);
+            return true;
+        }
+
+        false
+    }
+
+    fn report_paths_tried(&self, tried: u32) {
+        self.paths_tried.fetch_add(tried as u64, Ordering::Relaxed);
+        self.unreported_paths_tried
+            .fetch_add(tried as u64, Ordering::Relaxed);
+
+        // whoever holds the callback reports the paths tried by other branches in the meantime too
+        if let Some(mut callback) = self.progress_callback.try_lock() {
+            self.flush_paths_tried(&mut callback);
+        }
+    }
+
+    fn flush_paths_tried(&self, callback: &mut F) {
+        let unreported = self.unreported_paths_tried.swap(0, Ordering::Relaxed);
+        if unreported > 0 {
+            callback(SolverProgress::PathsTried(
+                u32::try_from(unreported).unwrap_or(u32::MAX),
+            ));
+        }
+    }
+
+    fn stats(&self, elapsed: Duration) -> SolverStats {
+        SolverStats {
+            paths_tried: self.paths_tried.load(Ordering::Relaxed),
+            nodes_expanded: self.nodes_expanded.load(Ordering::Relaxed),
+            leaves: self.leaves.load(Ordering::Relaxed),
+            pruned: self.pruned.load(Ordering::Relaxed),
+            deduplicated: self.deduplicated.load(Ordering::Relaxed),
+            elapsed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum ItemKey {
+    Item,
+    Sacrifice(u32),
+    Enchant(u32),
+}
+
+impl PartialOrd for ItemKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ItemKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self {
+            ItemKey::Item => match *other == ItemKey::Item {
+                true => std::cmp::Ordering::Equal,
+                false => std::cmp::Ordering::Less,
+            },
+            ItemKey::Sacrifice(index) => match other {
+                ItemKey::Item => std::cmp::Ordering::Greater,
+                ItemKey::Sacrifice(other_index) => index.cmp(other_index),
+                ItemKey::Enchant(_) => std::cmp::Ordering::Less,
+            },
+            ItemKey::Enchant(insertion_index) => match other {
+                ItemKey::Item | ItemKey::Sacrifice(_) => std::cmp::Ordering::Greater,
+                ItemKey::Enchant(other_insertion_index) => {
+                    insertion_index.cmp(other_insertion_index)
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct ItemKeyCombination {
+    pub combination: Vec<ItemKey>,
+    /// Resulting enchants sorted by id, same enchants are merged into one level
+    pub enchants: Vec<EnchantLevel>,
+    pub anvil_use_count: u32,
+    pub damage: u32,
+}
+
+impl ItemKeyCombination {
+    pub fn single_item(key: ItemKey, enchant: EnchantLevel, anvil_use_count: u32) -> Self {
+        ItemKeyCombination {
+            combination: Vec::from([key]),
+            enchants: Vec::from([enchant]),
+            anvil_use_count,
+            damage: 0,
+        }
+    }
+
+    pub fn multiple_items(
+        keys: Vec<ItemKey>,
+        enchants: Vec<EnchantLevel>,
+        anvil_use_count: u32,
+        damage: u32,
+    ) -> Self {
+        let mut combination = ItemKeyCombination {
+            combination: keys,
+            enchants: Vec::with_capacity(enchants.len()),
+            anvil_use_count,
+            damage,
+        };
+        for enchant in enchants {
+            combination.merge_enchant(enchant);
+        }
+        combination
+    }
+
+    fn enchant(&self, id: u32) -> Option<&EnchantLevel> {
+        self.enchants
+            .binary_search_by_key(&id, |e| e.id)
+            .ok()
+            .map(|index| &self.enchants[index])
+    }
+
+    fn merge_enchant(&mut self, enchant: EnchantLevel) {
+        match self.enchants.binary_search_by_key(&enchant.id, |e| e.id) {
+            Ok(index) => {
+                let existing = &mut self.enchants[index];
+                existing.level = existing.merged_level(enchant.level);
+            }
+            Err(index) => self.enchants.insert(index, enchant),
+        }
+    }
+
+    pub fn combine(
+        &self,
+        other: &ItemKeyCombination,
+        rules: &EnchantRules,
+        max_durability: u32,
+    ) -> Self {
+        let mut combined = self.clone();
+        combined.combination.extend(other.combination.clone());
+        combined.combination.sort();
+
+        for enchant in &other.enchants {
+            if !self.drops(enchant, rules) {
+                combined.merge_enchant(*enchant);
+            }
+        }
+
+        let new_use_count = u32::max(self.anvil_use_count, other.anvil_use_count) + 1;
+        combined.anvil_use_count = new_use_count;
+        combined.damage = self.repaired_damage(other, max_durability);
+
+        combined
+    }
+
+    /// Damage left after merging an item sacrifice into this one,
+    /// the sacrifice's durability plus a 12% bonus gets added
+    fn repaired_damage(&self, other: &ItemKeyCombination, max_durability: u32) -> u32 {
+        if !other.is_item() {
+            return self.damage;
+        }
+
+        let other_durability = max_durability.saturating_sub(other.damage);
+        self.damage
+            .saturating_sub(other_durability + max_durability * 12 / 100)
+    }
+
+    /// Units of repair material a single anvil use takes to repair this combination as far as
+    /// `available` units allow, an anvil takes at most 4 at once
+    fn material_units(&self, max_durability: u32, available: u32) -> u32 {
+        let per_unit = max_durability / 4;
+        if per_unit == 0 {
+            return 0;
+        }
+
+        self.damage.div_ceil(per_unit).min(available).min(4)
+    }
+
+    /// Extra cost of merging a sacrifice into this combination when it repairs the item
+    pub fn repair_cost(&self, other: &ItemKeyCombination, max_durability: u32) -> u32 {
+        match self.repaired_damage(other, max_durability) < self.damage {
+            true => 2,
+            false => 0,
+        }
+    }
+
+    /// Checks if this combination is the target item
+    pub fn is_target(&self) -> bool {
+        self.combination.contains(&ItemKey::Item)
+    }
+
+    pub fn use_penalty(&self) -> u32 {
+        PenaltyBounds::penalty(self.anvil_use_count)
+    }
+
+    /// Checks if this combination is an item rather than a book
+    pub fn is_item(&self) -> bool {
+        self.combination
+            .iter()
+            .any(|e| matches!(e, ItemKey::Item | ItemKey::Sacrifice(_)))
+    }
+
+    /// Checks if a sacrifice's enchant gets dropped for being incompatible with one on this combination
+    fn drops(&self, enchant: &EnchantLevel, rules: &EnchantRules) -> bool {
+        self.enchants
+            .iter()
+            .any(|e| rules.is_incompatible(e.id, enchant.id))
+    }
+
+    /// Enchants of a sacrifice that get dropped when merging it into this combination
+    fn dropped<'a>(
+        &'a self,
+        sacrifice: &'a ItemKeyCombination,
+        rules: &'a EnchantRules,
+    ) -> impl Iterator<Item = &'a EnchantLevel> {
+        sacrifice
+            .enchants
+            .iter()
+            .filter(|enchant| self.drops(enchant, rules))
+    }
+
+    /// Cost of a sacrifice enchant that gets dropped, Java charges a level for each of them
+    fn dropped_cost(edition: registry::Edition) -> u32 {
+        match edition {
+            registry::Edition::Java => 1,
+            registry::Edition::Bedrock => 0,
+        }
+    }
+
+    /// Cost of the enchants when merging a sacrifice into this combination
+    pub fn cost(
+        &self,
+        sacrifice: &ItemKeyCombination,
+        rules: &EnchantRules,
+        edition: registry::Edition,
+    ) -> u32 {
+        let is_item = sacrifice.is_item();
+
+        let mut total = 0;
+        for enchant in &sacrifice.enchants {
+            if self.drops(enchant, rules) {
+                total += ItemKeyCombination::dropped_cost(edition);
+                continue;
+            }
+
+            let multiplier = match is_item {
+                true => enchant.multiplier.item,
+                false => enchant.multiplier.book,
+            };
+
+            let existing = self.enchant(enchant.id);
+            let level = match edition {
+                // paid at the level the target ends up with, even if the sacrifice's level is lower
+                registry::Edition::Java => match existing {
+                    Some(existing) => existing.merged_level(enchant.level),
+                    None => enchant.level,
+                },
+                // only the levels gained on the target are paid
+                registry::Edition::Bedrock => match existing {
+                    Some(existing) => existing
+                        .merged_level(enchant.level)
+                        .saturating_sub(existing.level),
+                    None => enchant.level,
+                },
+            };
+
+            total += multiplier * level;
+        }
+        total
+    }
+
+    /// Enchants that reach a higher level than on either side when merging a sacrifice into this combination
+    pub fn upgrades(
+        &self,
+        sacrifice: &ItemKeyCombination,
+        rules: &EnchantRules,
+    ) -> Vec<EnchantLevel> {
+        sacrifice
+            .enchants
+            .iter()
+            .filter(|enchant| !self.drops(enchant, rules))
+            .filter_map(|enchant| {
+                let existing = self.enchant(enchant.id)?;
+                let level = existing.merged_level(enchant.level);
+                (level > existing.level && level > enchant.level)
+                    .then_some(EnchantLevel { level, ..*existing })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum StepKind {
+    /// The combination gets merged into `Step::left`
+    Combine(Arc<ItemKeyCombination>),
+    /// The target gets repaired with this many units of its repair material
+    Repair(u32),
+    /// The target only gets renamed
+    Rename,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    pub left: Arc<ItemKeyCombination>,
+    pub kind: StepKind,
+    pub cost: u32,
+    /// The item gets renamed in this step
+    pub renamed: bool,
+}
+
+/// Levels renaming adds to the anvil use it is done in
+const RENAME_COST: u32 = 1;
+
+impl Step {
+    /// Whether the rename can be done in this step, which has to be on the target
+    /// and stay below the cost limit with the extra level
+    fn can_fold_rename(&self, options: &SolverOptions) -> bool {
+        self.left.is_target()
+            && options
+                .cost_limit
+                .map(|limit| self.cost.saturating_add(RENAME_COST) < limit)
+                .unwrap_or(true)
+    }
+}
+
+pub struct Solver<'enchants> {
+    enchants: &'enchants [Enchant],
+    /// Distinct enchants used by the input, indexed by `EnchantLevel::id`
+    registry_enchants: Vec<registry::Enchant>,
+    options: SolverOptions,
+    items: Vec<Arc<ItemKeyCombination>>,
+    rules: EnchantRules,
+    penalty_bounds: PenaltyBounds,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum ResolvedStepItem {
+    Item,
+    /// Item of the same type sacrificed into the target, the index is into `SolverOptions::sacrifices`
+    Sacrifice(u32),
+    Enchant(Enchant),
+}
+
+/// What an anvil use does to the item on the left
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ResolvedStepKind {
+    /// `ResolvedStep::right` gets merged into it
+    Combine,
+    /// It gets repaired with this many units of its repair material
+    Repair(u32),
+    /// It only gets renamed
+    Rename,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolvedStep {
+    pub kind: ResolvedStepKind,
+    pub left: Vec<ResolvedStepItem>,
+    pub right: Vec<ResolvedStepItem>,
+    /// Enchants that got upgraded to a higher level by merging two equal levels
+    pub upgraded: Vec<Enchant>,
+    /// Enchants the result of the step has, with dropped ones left out and same ones merged
+    pub enchants: Vec<Enchant>,
+    /// The item gets renamed in this step
+    pub renamed: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolvedPath {
+    pub cost: u32,
+    /// Level cost of the most expensive step
+    pub max_step_cost: u32,
+    /// Experience points of all steps, each paid from level 0
+    pub experience: u32,
+    /// Prior work penalty of the finished item
+    pub final_penalty: u32,
+    /// Damage the finished item still has, when the repair materials ran out or repairing
+    /// further was too expensive
+    pub damage: u32,
+    /// Objective the path was chosen by
+    pub objective: SolverObjective,
+    pub steps: Vec<ResolvedStep>,
+}
+
+impl ResolvedPath {
+    /// Score of the path on its objective
+    pub fn score(&self) -> u32 {
+        match self.objective {
+            SolverObjective::Levels => self.cost,
+            SolverObjective::Experience => self.experience,
+            SolverObjective::MaxStep => self.max_step_cost,
+            SolverObjective::PriorWork => self.final_penalty,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SolverStatus {
+    /// A path was found
+    Solved,
+    /// Nothing was selected or the item already has every selected enchant
+    NothingToCombine,
+    /// Every possible path has a step that reaches the cost limit
+    TooExpensive,
+    /// The solve got cancelled, the path is the best one found until then if any
+    Cancelled,
+    /// The time limit was reached, the path is the best one found until then if any
+    OutOfTime,
+    /// Some of the selected enchants and the ones already on the item are incompatible
+    Incompatible,
+}
+
+/// Reported through the progress callback while solving
+#[derive(Debug, Clone)]
+pub enum SolverProgress {
+    /// This many more paths were tried since the last report
+    PathsTried(u32),
+    /// A path cheaper than every one found before
+    Improved(ResolvedPath),
+}
+
+/// Stops a running `Solver::solve` from another thread
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SolverStats {
+    /// Anvil steps whose cost got calculated
+    pub paths_tried: u64,
+    /// Incomplete paths whose next steps got explored
+    pub nodes_expanded: u64,
+    /// Complete paths reached
+    pub leaves: u64,
+    /// Paths skipped because their lower bound couldn't beat the best path found
+    pub pruned: u64,
+    /// Paths skipped because another path reached the same state with a score at least as good
+    pub deduplicated: u64,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SolverResult {
+    pub status: SolverStatus,
+    pub path: Option<ResolvedPath>,
+    /// No path scores lower than this on the objective, equal to the path score once it is proven
+    /// to be the best
+    pub lower_bound: u32,
+    /// Paths no other path beats on total cost, most expensive step and final prior work penalty,
+    /// sorted by total cost, only collected with `SolverOptions::pareto`
+    pub alternatives: Vec<ResolvedPath>,
+    pub stats: SolverStats,
+}
+
+impl<'enchants> Solver<'enchants> {
+    pub fn new(enchants: &'enchants [Enchant]) -> Self {
+        Solver::with_options(enchants, SolverOptions::default())
+    }
+
+    /// Creates a solver after checking that the enchants and the ones already on the item
+    /// can all be on `item` together
+    pub fn for_item(
+        enchants: &'enchants [Enchant],
+        item: registry::Item,
+        options: SolverOptions,
+    ) -> Result<Self, SolverError> {
+        let conflicts = Solver::conflicts(enchants, item, &options);
+        if !conflicts.is_empty() {
+            return Err(SolverError::Conflicts(conflicts));
+        }
+
+        Ok(Solver::with_options(enchants, options))
+    }
+
+    fn conflicts(
+        enchants: &[Enchant],
+        item: registry::Item,
+        options: &SolverOptions,
+    ) -> Vec<EnchantConflict> {
+        let enchants = enchants
+            .iter()
+            .chain(options.item.enchants.iter())
+            .collect::<Vec<_>>();
+        let mut conflicts = Solver::enchant_conflicts(&enchants, item);
+        conflicts.extend(Solver::anvil_use_conflict(
+            options.item.anvil_use_count,
+            options,
+        ));
+
+        for (index, book) in options.books.iter().enumerate() {
+            let enchants = book.enchants.iter().collect::<Vec<_>>();
+            let book_conflicts = Solver::enchant_conflicts(&enchants, item)
+                .into_iter()
+                .chain(Solver::anvil_use_conflict(book.anvil_use_count, options));
+            conflicts.extend(book_conflicts.map(|conflict| EnchantConflict::InBook {
+                index,
+                conflict: Box::new(conflict),
+            }));
+        }
+
+        for (index, sacrifice) in options.sacrifices.iter().enumerate() {
+            let enchants = sacrifice.enchants.iter().collect::<Vec<_>>();
+            let sacrifice_conflicts = Solver::enchant_conflicts(&enchants, item)
+                .into_iter()
+                .chain(Solver::anvil_use_conflict(
+                    sacrifice.anvil_use_count,
+                    options,
+                ));
+            conflicts.extend(
+                sacrifice_conflicts.map(|conflict| EnchantConflict::InSacrifice {
+                    index,
+                    conflict: Box::new(conflict),
+                }),
+            );
+        }
+
+        conflicts
+    }
+
+    /// Invalid levels, enchants that don't apply to the item and incompatible pairs among
+    /// enchants that end up on the same item or book
+    fn enchant_conflicts(enchants: &[&Enchant], item: registry::Item) -> Vec<EnchantConflict> {
+        let mut conflicts = Vec::new();
+
+        for (index, enchant) in enchants.iter().enumerate() {
+            let name = &enchant.enchant.name;
+
+            if enchant.level == 0 || enchant.level > enchant.enchant.level_max {
+                conflicts.push(EnchantConflict::InvalidLevel {
+                    enchant: name.clone(),
+                    level: enchant.level,
+                    level_max: enchant.enchant.level_max,
+                });
+            }
+
+            if !enchant.enchant.items.contains(&item) {
+                conflicts.push(EnchantConflict::NotApplicable {
+                    enchant: name.clone(),
+                    item,
+                });
+            }
+
+            for other in &enchants[index + 1..] {
+                let other_name = &other.enchant.name;
+                if enchant.enchant.incompatible.contains(other_name)
+                    || other.enchant.incompatible.contains(name)
+                {
+                    conflicts.push(EnchantConflict::Incompatible {
+                        enchant: name.clone(),
+                        incompatible: other_name.clone(),
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// The prior work penalty alone makes every anvil use of something worked on this often
+    /// too expensive, without a cost limit it still has to fit the level counter
+    fn anvil_use_conflict(
+        anvil_use_count: u32,
+        options: &SolverOptions,
+    ) -> Option<EnchantConflict> {
+        let penalty = PenaltyBounds::penalty(anvil_use_count);
+        let limit = options.cost_limit.unwrap_or(u32::MAX);
+        (penalty >= limit).then_some(EnchantConflict::TooManyAnvilUses { anvil_use_count })
+    }
+
+    pub fn with_options(enchants: &'enchants [Enchant], options: SolverOptions) -> Self {
+        let mut registry_enchants = Vec::new();
+
+        let target_enchants = options
+            .item
+            .enchants
+            .iter()
+            .map(|enchant| Solver::enchant_level(&mut registry_enchants, enchant))
+            .collect::<Vec<_>>();
+        let mut required = target_enchants
+            .iter()
+            .map(|enchant| (enchant.id, enchant.level))
+            .collect::<Vec<_>>();
+
+        let mut items = Vec::from([Arc::new(ItemKeyCombination::multiple_items(
+            Vec::from([ItemKey::Item]),
+            target_enchants,
+            options.item.anvil_use_count,
+            options.item.damage,
+        ))]);
+
+        for (index, enchant) in enchants.iter().enumerate() {
+            // only planning books for enchants the item doesn't have yet
+            if options.item.has_enchant(enchant) {
+                continue;
+            }
+
+            let enchant = Solver::enchant_level(&mut registry_enchants, enchant);
+            required.push((enchant.id, enchant.level));
+            items.push(Arc::new(ItemKeyCombination::single_item(
+                ItemKey::Enchant(index as u32),
+                enchant,
+                0,
+            )));
+        }
+
+        // inventory book enchants are indexed after the selected enchants
+        let mut index = enchants.len() as u32;
+        for book in &options.books {
+            if book.enchants.is_empty() {
+                continue;
+            }
+
+            // the whole book is a single leaf, its enchants can't be split apart
+            let mut keys = Vec::new();
+            let mut levels = Vec::new();
+            for enchant in &book.enchants {
+                keys.push(ItemKey::Enchant(index));
+                levels.push(Solver::enchant_level(&mut registry_enchants, enchant));
+                index += 1;
+            }
+
+            items.push(Arc::new(ItemKeyCombination::multiple_items(
+                keys,
+                levels,
+                book.anvil_use_count,
+                0,
+            )));
+        }
+
+        // sacrifice item enchants are indexed after inventory book enchants
+        for (sacrifice_index, sacrifice) in options.sacrifices.iter().enumerate() {
+            let mut keys = Vec::from([ItemKey::Sacrifice(sacrifice_index as u32)]);
+            let mut levels = Vec::new();
+            for enchant in &sacrifice.enchants {
+                keys.push(ItemKey::Enchant(index));
+                levels.push(Solver::enchant_level(&mut registry_enchants, enchant));
+                index += 1;
+            }
+
+            items.push(Arc::new(ItemKeyCombination::multiple_items(
+                keys,
+                levels,
+                sacrifice.anvil_use_count,
+                sacrifice.damage,
+            )));
+        }
+
+        let rules = EnchantRules::new(&registry_enchants, required);
+
+        Solver {
+            enchants,
+            registry_enchants,
+            options,
+            items,
+            rules,
+            penalty_bounds: PenaltyBounds::default(),
+        }
+    }
+
+    fn enchant_level(
+        registry_enchants: &mut Vec<registry::Enchant>,
+        enchant: &Enchant,
+    ) -> EnchantLevel {
+        let id = match registry_enchants
+            .iter()
+            .position(|e| e.name == enchant.enchant.name)
+        {
+            Some(id) => id,
+            None => {
+                registry_enchants.push(enchant.enchant.clone());
+                registry_enchants.len() - 1
+            }
+        };
+
+        EnchantLevel {
+            id: id as u32,
+            level: enchant.level,
+            level_max: enchant.enchant.level_max,
+            multiplier: EnchantMultiplier {
+                book: enchant.enchant.weight,
+                item: enchant.enchant.item_weight,
+            },
+        }
+    }
+
+    fn resolve_enchant_level(&self, enchant: &EnchantLevel) -> Enchant {
+        Enchant::new(
+            self.registry_enchants[enchant.id as usize].clone(),
+            enchant.level,
+        )
+    }
+
+    fn enchant(&self, index: usize) -> &Enchant {
+        if index < self.enchants.len() {
+            return &self.enchants[index];
+        }
+
+        self.options
+            .books
+            .iter()
+            .flat_map(|book| book.enchants.iter())
+            .chain(
+                self.options
+                    .sacrifices
+                    .iter()
+                    .flat_map(|sacrifice| sacrifice.enchants.iter()),
+            )
+            .nth(index - self.enchants.len())
+            .unwrap()
+    }
+
+    fn resolve_item_key(&self, item_key: &ItemKey) -> ResolvedStepItem {
+        match item_key {
+            ItemKey::Item => ResolvedStepItem::Item,
+            ItemKey::Sacrifice(index) => ResolvedStepItem::Sacrifice(*index),
+            ItemKey::Enchant(index) => {
+                let enchant = self.enchant(*index as usize);
+                ResolvedStepItem::Enchant(enchant.clone())
+            }
+        }
+    }
+
+    fn resolve_combination(&self, combination: &ItemKeyCombination) -> Vec<ResolvedStepItem> {
+        combination
+            .combination
+            .iter()
+            .map(|e| self.resolve_item_key(e))
+            .collect::<Vec<_>>()
+    }
+
+    fn resolve_path(&self, path: &Path) -> ResolvedPath {
+        let mut steps = Vec::new();
+
+        for step in &path.steps {
+            let left = self.resolve_combination(&step.left);
+            let (kind, right, upgraded, enchants) = match &step.kind {
+                StepKind::Combine(right) => (
+                    ResolvedStepKind::Combine,
+                    self.resolve_combination(right),
+                    step.left.upgrades(right, &self.rules),
+                    step.left
+                        .combine(right, &self.rules, self.options.max_durability)
+                        .enchants,
+                ),
+                StepKind::Repair(units) => (
+                    ResolvedStepKind::Repair(*units),
+                    Vec::new(),
+                    Vec::new(),
+                    step.left.enchants.clone(),
+                ),
+                StepKind::Rename => (
+                    ResolvedStepKind::Rename,
+                    Vec::new(),
+                    Vec::new(),
+                    step.left.enchants.clone(),
+                ),
+            };
+
+            steps.push(ResolvedStep {
+                kind,
+                left,
+                right,
+                upgraded: upgraded
+                    .iter()
+                    .map(|e| self.resolve_enchant_level(e))
+                    .collect(),
+                enchants: enchants
+                    .iter()
+                    .map(|e| self.resolve_enchant_level(e))
+                    .collect(),
+                renamed: step.renamed,
+            })
+        }
+
+        ResolvedPath {
+            cost: path.cost,
+            max_step_cost: path.max_cost,
+            experience: path.experience,
+            final_penalty: path.final_penalty(),
+            damage: path.final_damage(),
+            objective: self.options.objective,
+            steps,
+        }
+    }
+
+    /// Number of search tree levels whose branches are explored in parallel
+    #[cfg(not(target_arch = "wasm32"))]
+    const PARALLEL_DEPTH: usize = 2;
+
+    /// Paths the greedy strategy keeps after every step
+    const GREEDY_BEAM_WIDTH: usize = 64;
+
+    /// Records a complete path, reporting it if it is the best one so far
+    fn complete<F>(&self, path: Path, search: &Search<F>)
+    where
+        F: FnMut(SolverProgress) + Send,
+    {
+        search.leaves.fetch_add(1, Ordering::Relaxed);
+
+        if self.options.pareto {
+            search.pareto_front.lock().insert(path.clone());
+        }
+
+        let improvement = {
+            let mut best_path = search.best_path.lock();
+            match best_path
+                .as_ref()
+                .map(|e| path.is_more_effective(e, self.options.objective))
+                .unwrap_or(true)
+            {
+                true => {
+                    search
+                        .best_score
+                        .store(Search::<F>::score(&path, &self.options), Ordering::Relaxed);
+                    *best_path = Some(path.clone());
+                    Some(search.improvements.fetch_add(1, Ordering::Relaxed) + 1)
+                }
+                false => None,
+            }
+        };
+
+        // resolving and reporting without holding `best_path`, so other branches don't
+        // wait on the callback, an improvement overtaken by a later one is skipped
+        if let Some(improvement) = improvement {
+            let resolved = self.resolve_path(&path);
+            let mut callback = search.progress_callback.lock();
+            if improvement > search.reported_improvement.load(Ordering::Relaxed) {
+                search
+                    .reported_improvement
+                    .store(improvement, Ordering::Relaxed);
+                callback(SolverProgress::Improved(resolved));
+            }
+        }
+    }
+
+    /// Builds paths a step at a time, only keeping the `GREEDY_BEAM_WIDTH` most promising
+    /// ones after every step instead of backtracking, so it takes about
+    /// `GREEDY_BEAM_WIDTH * n^3` tries for n combinations
+    ///
+    /// Keeping more than one path lets it get around steps that turn out too expensive later
+    fn solve_greedy<F>(&self, incomplete_path: &Path, search: &Search<F>)
+    where
+        F: FnMut(SolverProgress) + Send,
+    {
+        let mut beam = Vec::from([incomplete_path.clone()]);
+
+        while !beam.is_empty() && !search.stopped() {
+            let mut next = Vec::new();
+            for path in &beam {
+                let (paths, tried, duplicates) = path.explode(&self.options, &self.rules);
+
+                search.nodes_expanded.fetch_add(1, Ordering::Relaxed);
+                search
+                    .deduplicated
+                    .fetch_add(duplicates as u64, Ordering::Relaxed);
+                search.report_paths_tried(tried);
+
+                next.extend(paths);
+            }
+
+            let mut incomplete = Vec::new();
+            for path in next {
+                if !search.visited.visit(&path, &self.options) {
+                    search.deduplicated.fetch_add(1, Ordering::Relaxed);
+                } else if path.remaining.len() > 1 {
+                    incomplete.push(path);
+                } else {
+                    self.complete(path, search);
+                }
+            }
+
+            let count = incomplete.len();
+            beam = self.most_promising(incomplete);
+            search
+                .pruned
+                .fetch_add((count - beam.len()) as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Keeps the paths with the lowest lower bound and the ones with the lowest prior work
+    /// penalties, `GREEDY_BEAM_WIDTH` in total
+    ///
+    /// The lower bound favors cheap steps now, which can leave only steps over the cost limit
+    /// for later, low penalties keep the remaining steps cheap
+    fn most_promising(&self, paths: Vec<Path>) -> Vec<Path> {
+        if paths.len() <= Solver::GREEDY_BEAM_WIDTH {
+            return paths;
+        }
+
+        let mut paths = paths
+            .into_iter()
+            .map(|path| {
+                let lower_bound =
+                    path.lower_bound(&self.options, &self.rules, &self.penalty_bounds);
+                let penalties = path
+                    .remaining
+                    .iter()
+                    .map(|e| e.use_penalty())
+                    .fold(0, u32::saturating_add);
+                (lower_bound, penalties, path)
+            })
+            .collect::<Vec<_>>();
+
+        // stable sorts, so equally promising paths stay in the order they were generated
+        paths.sort_by_key(|(lower_bound, penalties, _)| (*penalties, *lower_bound));
+        let mut rest = paths.split_off(Solver::GREEDY_BEAM_WIDTH / 2);
+
+        rest.sort_by_key(|(lower_bound, _, _)| *lower_bound);
+        rest.truncate(Solver::GREEDY_BEAM_WIDTH - paths.len());
+        paths.extend(rest);
+
+        paths.into_iter().map(|(_, _, path)| path).collect()
+    }
+
+    fn solve_inner<F>(&self, incomplete_path: &Path, depth: usize, search: &Search<F>)
+    where
+        F: FnMut(SolverProgress) + Send,
+    {
+        if search.stopped() {
+            return;
+        }
+
+        let (paths, tried, duplicates) = incomplete_path.explode(&self.options, &self.rules);
+
+        search.nodes_expanded.fetch_add(1, Ordering::Relaxed);
+        search
+            .deduplicated
+            .fetch_add(duplicates as u64, Ordering::Relaxed);
+        search.report_paths_tried(tried);
+
+        // exploring the most promising paths first so worse ones can be cut off earlier,
+        // paths whose state another branch already reached at no higher cost are explored from there
+        let mut paths = paths
+            .into_iter()
+            .filter(|path| {
+                let visited = search.visited.visit(path, &self.options);
+                if !visited {
+                    search.deduplicated.fetch_add(1, Ordering::Relaxed);
+                }
+                visited
+            })
+            .map(|path| {
+                let (primary, secondary) =
+                    path.lower_bound(&self.options, &self.rules, &self.penalty_bounds);
+                (Search::<F>::pack_score(primary, secondary), path)
+            })
+            .collect::<Vec<_>>();
+        paths.sort_by_key(|(lower_bound, _)| *lower_bound);
+
+        let explore = |(lower_bound, path): (u64, Path)| {
+            // in pareto mode a path can still be the best for the objective when the front covers it
+            let pruned = lower_bound > search.best_score.load(Ordering::Relaxed)
+                && (!self.options.pareto
+                    || search.pareto_front.lock().covers(path.pareto_bound(
+                        &self.options,
+                        &self.rules,
+                        &self.penalty_bounds,
+                    )));
+            if pruned {
+                return false;
+            }
+
+            if path.remaining.len() > 1 {
+                self.solve_inner(&path, depth + 1, search);
+            } else {
+                self.complete(path, search);
+            }
+
+            true
+        };
+
+        // wasm has no rayon thread pool set up, so the search stays on the worker there
+        #[cfg(not(target_arch = "wasm32"))]
+        if depth < Solver::PARALLEL_DEPTH {
+            paths.into_par_iter().for_each(|path| {
+                if !explore(path) {
+                    search.pruned.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+            return;
+        }
+
+        let count = paths.len();
+        for (index, path) in paths.into_iter().enumerate() {
+            if explore(path) {
+                continue;
+            }
+
+            if self.options.pareto {
+                search.pruned.fetch_add(1, Ordering::Relaxed);
+            } else {
+                // sorted by lower bound, so none of the remaining paths can be better either
+                search
+                    .pruned
+                    .fetch_add((count - index) as u64, Ordering::Relaxed);
+                break;
+            }
+        }
+    }
+
+    pub fn solve(
+        &self,
+        cancellation: &CancellationToken,
+        progress_callback: impl FnMut(SolverProgress) + Send,
+    ) -> SolverResult {
+        let incomplete_path = Path {
+            cost: 0,
+            max_cost: 0,
+            experience: 0,
+            remaining: Vec::from_iter(self.items.clone()),
+            steps: Vec::with_capacity(self.items.len()),
+            repair_materials: self.options.repair_materials,
+            state: 0,
+            order: 0,
+        };
+        let start = Instant::now();
+        let deadline = self.options.time_limit.map(|limit| start + limit);
+        let search = Search::new(progress_callback, cancellation.clone(), deadline);
+
+        // no path can keep incompatible enchants together, so there is nothing to search
+        let conflicts = self.rules.has_conflicts();
+        if !conflicts {
+            match self.options.strategy {
+                SolverStrategy::Exact => self.solve_inner(&incomplete_path, 0, &search),
+                SolverStrategy::Greedy => self.solve_greedy(&incomplete_path, &search),
+            }
+        }
+
+        search.flush_paths_tried(&mut search.progress_callback.lock());
+        let stats = search.stats(start.elapsed());
+        let mut best_path = search.best_path.into_inner();
+
+        // with nothing to combine, repairing and renaming are the only anvil uses
+        if best_path.is_none() && self.items.len() <= 1 {
+            let mut path = incomplete_path.clone();
+            if path.finish(&self.options) && !path.steps.is_empty() {
+                best_path = Some(path);
+            }
+        }
+
+        let mut alternatives = search.pareto_front.into_inner().paths;
+        alternatives.sort_by_key(|path| path.pareto_score());
+        let alternatives = alternatives
+            .iter()
+            .map(|path| self.resolve_path(path))
+            .collect();
+
+        let status = match best_path {
+            _ if cancellation.is_cancelled() => SolverStatus::Cancelled,
+            _ if search.timed_out.load(Ordering::Relaxed) => SolverStatus::OutOfTime,
+            Some(_) => SolverStatus::Solved,
+            None if conflicts => SolverStatus::Incompatible,
+            None if self.items.len() <= 1 => SolverStatus::NothingToCombine,
+            None => SolverStatus::TooExpensive,
+        };
+
+        // an exhaustive search proves its path to be the best
+        let lower_bound = match (&best_path, status, self.options.strategy) {
+            (Some(path), SolverStatus::Solved, SolverStrategy::Exact) => {
+                self.options.objective.score(path).0
+            }
+            _ => {
+                incomplete_path
+                    .lower_bound(&self.options, &self.rules, &self.penalty_bounds)
+                    .0
+            }
+        };
+        let resolved_path = best_path.map(|path| self.resolve_path(&path));
+
+        SolverResult {
+            status,
+            path: resolved_path,
+            lower_bound,
+            alternatives,
+            stats,
+        }
+    }
+}
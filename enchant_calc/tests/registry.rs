@@ -0,0 +1,240 @@
+use enchant_calc::registry::{
+    self, Edition, Item, Registry, RegistryFormat, ValidationIssue, Version,
+};
+
+#[test]
+fn builtin_registry_is_valid() {
+    let report = registry::builtin_registry().validate();
+    assert!(report.is_valid(), "{}", report);
+}
+
+#[test]
+fn shipped_registries_are_valid() {
+    // validating before `for_edition` and `for_version` clean up, which would hide broken data
+    for edition in [Edition::Java, Edition::Bedrock] {
+        let registry = Registry {
+            enchants: registry::builtin_registry()
+                .enchants
+                .iter()
+                // an override without items means the enchant doesn't exist in that edition
+                .filter(|(_, enchant)| {
+                    let removed = enchant
+                        .bedrock
+                        .as_ref()
+                        .and_then(|overrides| overrides.items.as_ref())
+                        .map(|items| items.is_empty())
+                        .unwrap_or(false);
+                    edition == Edition::Java || !removed
+                })
+                .map(|(key, enchant)| (key.clone(), enchant.for_edition(edition)))
+                .collect(),
+        };
+
+        let report = registry.validate();
+        assert!(report.is_valid(), "{}:\n{}", edition, report);
+    }
+}
+
+#[test]
+fn validate_reports_inconsistencies() {
+    let json = r#"
+    {
+        "enchants": {
+            "Sharpness": {
+                "name": "Sharpness",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 0,
+                "incompatible": ["Smite", ""],
+                "items": ["sword"]
+            },
+            "Smite": {
+                "name": "Smite",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 2,
+                "incompatible": [],
+                "items": []
+            }
+        }
+    }
+    "#;
+
+    let registry = Registry::from_reader(json.as_bytes(), RegistryFormat::Json).unwrap();
+    let issues = registry.validate().issues;
+
+    assert!(issues.contains(&ValidationIssue::ZeroWeight {
+        enchant: "Sharpness".to_string()
+    }));
+    assert!(issues.contains(&ValidationIssue::UnknownIncompatible {
+        enchant: "Sharpness".to_string(),
+        incompatible: "".to_string()
+    }));
+    assert!(issues.contains(&ValidationIssue::AsymmetricIncompatible {
+        enchant: "Sharpness".to_string(),
+        incompatible: "Smite".to_string()
+    }));
+    assert!(issues.contains(&ValidationIssue::NoItems {
+        enchant: "Smite".to_string()
+    }));
+}
+
+#[test]
+fn bedrock_overrides_are_applied() {
+    let java = registry::registry(Edition::Java, Version::LATEST);
+    let bedrock = registry::registry(Edition::Bedrock, Version::LATEST);
+
+    assert!(java.enchants.contains_key("Sweeping Edge"));
+    assert!(!bedrock.enchants.contains_key("Sweeping Edge"));
+
+    assert!(!java.enchants["Silk Touch"].items.contains(&Item::Shears));
+    assert!(bedrock.enchants["Silk Touch"].items.contains(&Item::Shears));
+    assert_eq!(
+        java.enchants["Impaling"].weight,
+        bedrock.enchants["Impaling"].weight
+    );
+}
+
+#[test]
+fn legacy_bedrock_shears_are_resolved() {
+    let json = r#"
+    {
+        "enchants": {
+            "Silk Touch": {
+                "name": "Silk Touch",
+                "levelMax": 1,
+                "weight": 4,
+                "itemWeight": 8,
+                "incompatible": [],
+                "items": ["pickaxe", "shears_bedrock"]
+            }
+        }
+    }
+    "#;
+
+    let registry = Registry::from_reader(json.as_bytes(), RegistryFormat::Json).unwrap();
+    let java = registry.for_edition(Edition::Java);
+    let bedrock = registry.for_edition(Edition::Bedrock);
+
+    assert_eq!(java.enchants["Silk Touch"].items, vec![Item::Pickaxe]);
+    assert_eq!(
+        bedrock.enchants["Silk Touch"].items,
+        vec![Item::Pickaxe, Item::Shears]
+    );
+}
+
+#[test]
+fn registries_without_item_weights_load() {
+    let json = r#"
+    {
+        "enchants": {
+            "Sharpness": {
+                "name": "Sharpness",
+                "levelMax": 5,
+                "weight": 1,
+                "incompatible": [],
+                "items": ["sword"]
+            }
+        }
+    }
+    "#;
+
+    let registry = Registry::from_reader(json.as_bytes(), RegistryFormat::Json).unwrap();
+    assert_eq!(registry.enchants["Sharpness"].item_weight, 1);
+}
+
+#[test]
+fn incompatible_enchants_are_resolved_by_name() {
+    let json = r#"
+    {
+        "enchants": {
+            "sharpness": {
+                "name": "Sharpness",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 1,
+                "incompatible": ["Smite"],
+                "items": ["sword"]
+            },
+            "smite": {
+                "name": "Smite",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 2,
+                "incompatible": ["Sharpness"],
+                "items": ["sword"]
+            }
+        }
+    }
+    "#;
+
+    let registry = Registry::from_reader(json.as_bytes(), RegistryFormat::Json).unwrap();
+    let issues = registry.validate().issues;
+
+    assert!(issues
+        .iter()
+        .all(|issue| matches!(issue, ValidationIssue::MismatchedKey { .. })));
+    assert_eq!(
+        registry.for_edition(Edition::Java).enchants["sharpness"].incompatible,
+        vec!["Smite".to_string()]
+    );
+
+    let unknown = json.replace("[\"Smite\"]", "[\"smite\"]");
+    assert!(Registry::from_reader(unknown.as_bytes(), RegistryFormat::Json).is_err());
+}
+
+#[test]
+fn validate_reports_duplicates() {
+    let json = r#"
+    {
+        "enchants": {
+            "Sharpness": {
+                "name": "Sharpness",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 1,
+                "incompatible": ["Smite", "Smite"],
+                "items": ["sword", "axe", "sword"]
+            },
+            "Smite": {
+                "name": "Smite",
+                "levelMax": 5,
+                "weight": 1,
+                "itemWeight": 2,
+                "incompatible": ["Sharpness"],
+                "items": ["sword"]
+            }
+        }
+    }
+    "#;
+
+    let registry = Registry::from_reader(json.as_bytes(), RegistryFormat::Json).unwrap();
+    assert_eq!(
+        registry.validate().issues,
+        vec![
+            ValidationIssue::DuplicateItem {
+                enchant: "Sharpness".to_string(),
+                item: Item::Sword
+            },
+            ValidationIssue::DuplicateIncompatible {
+                enchant: "Sharpness".to_string(),
+                incompatible: "Smite".to_string()
+            },
+        ]
+    );
+
+    let duplicate_key = json.replace("\"Smite\": {", "\"Sharpness\": {");
+    assert!(matches!(
+        Registry::from_reader(duplicate_key.as_bytes(), RegistryFormat::Json),
+        Err(registry::error::RegistryError::DuplicateKey(key)) if key == "Sharpness"
+    ));
+}
+
+#[test]
+fn silk_touch_applies_to_tools() {
+    let java = &registry::registry(Edition::Java, Version::LATEST).enchants["Silk Touch"];
+    assert_eq!(
+        java.items,
+        vec![Item::Pickaxe, Item::Shovel, Item::Axe, Item::Hoe]
+    );
+}
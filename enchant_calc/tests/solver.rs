@@ -0,0 +1,873 @@
+use enchant_calc::{
+    registry::{self, REGISTRY},
+    solver,
+};
+
+fn enchants(enchants: &[(&str, u32)]) -> Vec<solver::Enchant> {
+    enchants
+        .iter()
+        .map(|(name, level)| solver::Enchant::new(REGISTRY.enchants[*name].clone(), *level))
+        .collect()
+}
+
+#[test]
+fn progress_matches_result() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+    ]);
+
+    let mut reported = 0;
+    let mut improved = Vec::new();
+    let result =
+        solver::Solver::new(&enchants).solve(&solver::CancellationToken::new(), |progress| {
+            match progress {
+                solver::SolverProgress::PathsTried(tried) => reported += tried as u64,
+                solver::SolverProgress::Improved(path) => improved.push(path.cost),
+            }
+        });
+
+    assert_eq!(result.status, solver::SolverStatus::Solved);
+    assert_eq!(result.stats.paths_tried, reported);
+    assert_eq!(improved.last(), result.path.as_ref().map(|path| &path.cost));
+    assert!(improved.windows(2).all(|costs| costs[0] >= costs[1]));
+    assert!(result.stats.nodes_expanded > 0);
+    assert!(result.stats.leaves > 0);
+}
+
+#[test]
+fn cancelled_solve_stops() {
+    let enchants = enchants(&[("Protection", 4), ("Unbreaking", 3), ("Mending", 1)]);
+
+    let cancellation = solver::CancellationToken::new();
+    cancellation.cancel();
+    let result = solver::Solver::new(&enchants).solve(&cancellation, |_| {});
+
+    assert_eq!(result.status, solver::SolverStatus::Cancelled);
+    assert_eq!(result.stats.nodes_expanded, 0);
+}
+
+#[test]
+fn greedy_is_within_lower_bound() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+    ]);
+
+    let exact = solver::Solver::new(&enchants).solve(&solver::CancellationToken::new(), |_| {});
+    let greedy = solver::Solver::with_options(
+        &enchants,
+        solver::SolverOptions {
+            strategy: solver::SolverStrategy::Greedy,
+            ..Default::default()
+        },
+    )
+    .solve(&solver::CancellationToken::new(), |_| {});
+
+    let exact_cost = exact.path.unwrap().cost;
+    let greedy_cost = greedy.path.unwrap().cost;
+
+    assert_eq!(exact.lower_bound, exact_cost);
+    assert!(greedy.lower_bound <= exact_cost);
+    assert!(greedy_cost >= exact_cost);
+}
+
+#[test]
+fn greedy_expands_a_bounded_number_of_paths() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+        ("Depth Strider", 3),
+        ("Soul Speed", 3),
+        ("Swift Sneak", 3),
+        ("Curse of Binding", 1),
+        ("Curse of Vanishing", 1),
+    ]);
+
+    let greedy = solver::Solver::with_options(
+        &enchants,
+        solver::SolverOptions {
+            strategy: solver::SolverStrategy::Greedy,
+            ..Default::default()
+        },
+    )
+    .solve(&solver::CancellationToken::new(), |_| {});
+
+    // at most a beam of paths is expanded for each of the 10 steps
+    assert!(greedy.stats.nodes_expanded <= 64 * 10);
+    assert!(greedy.lower_bound <= greedy.path.unwrap().cost);
+}
+
+#[test]
+fn level_experience_follows_vanilla_curve() {
+    assert_eq!(solver::level_experience(0), 0);
+    assert_eq!(solver::level_experience(16), 352);
+    assert_eq!(solver::level_experience(17), 394);
+    assert_eq!(solver::level_experience(30), 1395);
+    assert_eq!(solver::level_experience(31), 1507);
+    assert_eq!(solver::level_experience(32), 1628);
+}
+
+#[test]
+fn objective_is_reported() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+    ]);
+
+    let solve = |objective| {
+        solver::Solver::with_options(
+            &enchants,
+            solver::SolverOptions {
+                objective,
+                ..Default::default()
+            },
+        )
+        .solve(&solver::CancellationToken::new(), |_| {})
+    };
+
+    let levels = solve(solver::SolverObjective::Levels).path.unwrap();
+    let experience = solve(solver::SolverObjective::Experience);
+    let max_step = solve(solver::SolverObjective::MaxStep);
+    let prior_work = solve(solver::SolverObjective::PriorWork);
+
+    assert_eq!(
+        experience.lower_bound,
+        experience.path.as_ref().unwrap().score()
+    );
+    assert_eq!(
+        max_step.lower_bound,
+        max_step.path.as_ref().unwrap().score()
+    );
+    assert!(experience.path.unwrap().experience <= levels.experience);
+    assert!(max_step.path.unwrap().max_step_cost <= levels.max_step_cost);
+    assert_eq!(
+        prior_work.lower_bound,
+        prior_work.path.as_ref().unwrap().score()
+    );
+    assert!(prior_work.path.unwrap().final_penalty <= levels.final_penalty);
+}
+
+#[test]
+fn pareto_front_has_no_dominated_paths() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+        ("Depth Strider", 3),
+        ("Curse of Binding", 1),
+    ]);
+
+    let result = solver::Solver::with_options(
+        &enchants,
+        solver::SolverOptions {
+            pareto: true,
+            ..Default::default()
+        },
+    )
+    .solve(&solver::CancellationToken::new(), |_| {});
+
+    let path = result.path.unwrap();
+    let alternatives = result.alternatives;
+
+    assert!(!alternatives.is_empty());
+    assert_eq!(alternatives[0].cost, path.cost);
+    for a in &alternatives {
+        for b in &alternatives {
+            let dominates = a.cost <= b.cost
+                && a.max_step_cost <= b.max_step_cost
+                && a.final_penalty <= b.final_penalty;
+            assert!(std::ptr::eq(a, b) || !dominates);
+        }
+    }
+}
+
+#[test]
+fn pareto_front_keeps_the_best_path_for_the_objective() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+        ("Curse of Binding", 1),
+    ]);
+
+    for objective in [
+        solver::SolverObjective::Levels,
+        solver::SolverObjective::Experience,
+        solver::SolverObjective::MaxStep,
+        solver::SolverObjective::PriorWork,
+    ] {
+        let solve = |pareto| {
+            solver::Solver::with_options(
+                &enchants,
+                solver::SolverOptions {
+                    objective,
+                    pareto,
+                    ..Default::default()
+                },
+            )
+            .solve(&solver::CancellationToken::new(), |_| {})
+        };
+
+        let single = solve(false);
+        let front = solve(true);
+
+        assert_eq!(
+            front.path.as_ref().unwrap().score(),
+            single.path.as_ref().unwrap().score(),
+            "{objective}"
+        );
+        assert_eq!(front.lower_bound, single.lower_bound, "{objective}");
+    }
+}
+
+#[test]
+fn for_item_lists_conflicts() {
+    let conflicting = enchants(&[("Sharpness", 5), ("Smite", 5), ("Protection", 4)]);
+
+    let error = solver::Solver::for_item(
+        &conflicting,
+        registry::Item::Sword,
+        solver::SolverOptions::default(),
+    )
+    .err()
+    .unwrap();
+    let solver::error::SolverError::Conflicts(conflicts) = error;
+
+    assert_eq!(
+        conflicts,
+        vec![
+            solver::error::EnchantConflict::Incompatible {
+                enchant: "Sharpness".to_string(),
+                incompatible: "Smite".to_string()
+            },
+            solver::error::EnchantConflict::NotApplicable {
+                enchant: "Protection".to_string(),
+                item: registry::Item::Sword
+            },
+        ]
+    );
+
+    let enchants = enchants(&[("Sharpness", 5), ("Unbreaking", 3)]);
+    assert!(solver::Solver::for_item(
+        &enchants,
+        registry::Item::Sword,
+        solver::SolverOptions::default()
+    )
+    .is_ok());
+
+    // books and sacrifices are checked on their own and listed by their index
+    let options = solver::SolverOptions {
+        books: vec![
+            solver::InventoryBook {
+                enchants: self::enchants(&[("Protection", 4)]),
+                anvil_use_count: 0,
+            },
+            solver::InventoryBook {
+                enchants: self::enchants(&[("Smite", 2), ("Bane of Arthropods", 1)]),
+                anvil_use_count: 0,
+            },
+        ],
+        sacrifices: vec![solver::TargetItem {
+            enchants: self::enchants(&[("Looting", 4)]),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let error = solver::Solver::for_item(&enchants, registry::Item::Sword, options)
+        .err()
+        .unwrap();
+    let solver::error::SolverError::Conflicts(conflicts) = error;
+
+    assert_eq!(
+        conflicts,
+        vec![
+            solver::error::EnchantConflict::InBook {
+                index: 0,
+                conflict: Box::new(solver::error::EnchantConflict::NotApplicable {
+                    enchant: "Protection".to_string(),
+                    item: registry::Item::Sword
+                }),
+            },
+            solver::error::EnchantConflict::InBook {
+                index: 1,
+                conflict: Box::new(solver::error::EnchantConflict::Incompatible {
+                    enchant: "Smite".to_string(),
+                    incompatible: "Bane of Arthropods".to_string()
+                }),
+            },
+            solver::error::EnchantConflict::InSacrifice {
+                index: 0,
+                conflict: Box::new(solver::error::EnchantConflict::InvalidLevel {
+                    enchant: "Looting".to_string(),
+                    level: 4,
+                    level_max: 3
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn for_item_rejects_unusable_prior_work() {
+    let enchants = enchants(&[("Sharpness", 5), ("Unbreaking", 3)]);
+    let worn = |anvil_use_count, cost_limit| solver::SolverOptions {
+        cost_limit,
+        item: solver::TargetItem {
+            anvil_use_count,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for (anvil_use_count, cost_limit) in [(6, Some(solver::TOO_EXPENSIVE_COST)), (32, None)] {
+        let error = solver::Solver::for_item(
+            &enchants,
+            registry::Item::Sword,
+            worn(anvil_use_count, cost_limit),
+        )
+        .err()
+        .unwrap();
+        let solver::error::SolverError::Conflicts(conflicts) = error;
+
+        assert_eq!(
+            conflicts,
+            vec![solver::error::EnchantConflict::TooManyAnvilUses { anvil_use_count }]
+        );
+    }
+
+    // creative mode has no limit, so the solver has to cope with huge penalties
+    let result = solver::Solver::with_options(&enchants, worn(40, None))
+        .solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(result.status, solver::SolverStatus::Solved);
+
+    let result = solver::Solver::for_item(
+        &enchants,
+        registry::Item::Sword,
+        worn(4, Some(solver::TOO_EXPENSIVE_COST)),
+    )
+    .unwrap()
+    .solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(result.status, solver::SolverStatus::Solved);
+}
+
+#[test]
+fn incompatible_enchants_are_dropped_or_rejected() {
+    let conflicting = enchants(&[("Sharpness", 5), ("Smite", 5)]);
+    let result = solver::Solver::new(&conflicting).solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(result.status, solver::SolverStatus::Incompatible);
+    assert!(result.path.is_none());
+
+    // the book's Smite has to be dropped, which must not take the wanted Looting with it
+    let enchants = enchants(&[("Sharpness", 5), ("Looting", 3)]);
+    let options = solver::SolverOptions {
+        books: vec![solver::InventoryBook {
+            enchants: vec![solver::Enchant::new(REGISTRY.enchants["Smite"].clone(), 5)],
+            anvil_use_count: 0,
+        }],
+        ..Default::default()
+    };
+    let with_book = solver::Solver::with_options(&enchants, options)
+        .solve(&solver::CancellationToken::new(), |_| {});
+    let without_book =
+        solver::Solver::new(&enchants).solve(&solver::CancellationToken::new(), |_| {});
+
+    assert_eq!(with_book.status, solver::SolverStatus::Solved);
+    assert!(with_book.path.unwrap().cost > without_book.path.unwrap().cost);
+}
+
+#[test]
+fn finished_item_has_the_required_levels() {
+    let enchants = enchants(&[("Bane of Arthropods", 3), ("Unbreaking", 3)]);
+    let options = solver::SolverOptions {
+        books: vec![solver::InventoryBook {
+            enchants: vec![solver::Enchant::new(
+                REGISTRY.enchants["Bane of Arthropods"].clone(),
+                1,
+            )],
+            anvil_use_count: 0,
+        }],
+        ..Default::default()
+    };
+    let result = solver::Solver::with_options(&enchants, options)
+        .solve(&solver::CancellationToken::new(), |_| {});
+
+    // the lower level book can't stand in for the selected level
+    let path = result.path.unwrap();
+    let finished = &path.steps.last().unwrap().enchants;
+    assert!(finished
+        .iter()
+        .any(|e| e.enchant.name == "Bane of Arthropods" && e.level == 3));
+}
+
+#[test]
+fn merged_levels_are_capped_and_paid_at_the_result() {
+    let sharpness = REGISTRY.enchants["Sharpness"].clone();
+    let solve = |book_level, edition| {
+        let options = solver::SolverOptions {
+            item: solver::TargetItem {
+                enchants: vec![solver::Enchant::new(sharpness.clone(), 5)],
+                ..Default::default()
+            },
+            books: vec![solver::InventoryBook {
+                enchants: vec![solver::Enchant::new(sharpness.clone(), book_level)],
+                anvil_use_count: 0,
+            }],
+            edition,
+            ..Default::default()
+        };
+        solver::Solver::with_options(&[], options)
+            .solve(&solver::CancellationToken::new(), |_| {})
+            .path
+            .unwrap()
+    };
+
+    // a lower level keeps the target's level, and Java still pays for the level the target has
+    for book_level in [3, 5] {
+        let path = solve(book_level, registry::Edition::Java);
+        assert_eq!(path.cost, 5 * sharpness.weight);
+
+        let step = &path.steps[0];
+        assert!(step.upgraded.is_empty());
+        assert_eq!(step.enchants.len(), 1);
+        assert_eq!(step.enchants[0].enchant.name, "Sharpness");
+        assert_eq!(step.enchants[0].level, 5);
+    }
+
+    assert_eq!(solve(3, registry::Edition::Bedrock).cost, 0);
+}
+
+#[test]
+fn rename_is_folded_into_the_cheapest_target_step() {
+    let enchants = enchants(&[("Protection", 4), ("Unbreaking", 3), ("Mending", 1)]);
+    let renamed_options = |anvil_use_count| solver::SolverOptions {
+        item: solver::TargetItem {
+            anvil_use_count,
+            ..Default::default()
+        },
+        rename: Some("Helmet".to_string()),
+        ..Default::default()
+    };
+
+    let plain = solver::Solver::new(&enchants)
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    let renamed = solver::Solver::with_options(&enchants, renamed_options(0))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+
+    assert_eq!(renamed.cost, plain.cost + 1);
+    assert_eq!(renamed.final_penalty, plain.final_penalty);
+    assert_eq!(renamed.steps.iter().filter(|step| step.renamed).count(), 1);
+    assert!(renamed.steps.iter().all(|step| !step.renamed
+        || step
+            .left
+            .iter()
+            .any(|item| matches!(item, solver::ResolvedStepItem::Item))));
+
+    // with nothing to combine the rename is a step of its own paying the prior work penalty
+    let alone = solver::Solver::with_options(&[], renamed_options(2))
+        .solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(alone.status, solver::SolverStatus::Solved);
+
+    let alone = alone.path.unwrap();
+    assert_eq!(alone.cost, 1 + 3);
+    assert_eq!(alone.final_penalty, 7);
+    assert!(alone.steps[0].renamed && alone.steps[0].right.is_empty());
+
+    // Java caps a rename on its own below the limit however high the penalty, Bedrock refuses it
+    let worn = |edition| {
+        solver::Solver::with_options(
+            &[],
+            solver::SolverOptions {
+                edition,
+                ..renamed_options(6)
+            },
+        )
+        .solve(&solver::CancellationToken::new(), |_| {})
+    };
+    let java = worn(registry::Edition::Java).path.unwrap();
+    assert_eq!(java.cost, solver::TOO_EXPENSIVE_COST - 1);
+    assert_eq!(java.final_penalty, 127);
+    assert!(worn(registry::Edition::Bedrock).path.is_none());
+}
+
+#[test]
+fn material_repairs_are_scheduled_with_the_merges() {
+    let enchants = enchants(&[("Protection", 4), ("Unbreaking", 3)]);
+    let options = |repair_materials| solver::SolverOptions {
+        item: solver::TargetItem {
+            anvil_use_count: 2,
+            damage: 1000,
+            ..Default::default()
+        },
+        max_durability: 1561,
+        repair_materials,
+        ..Default::default()
+    };
+
+    let path = solver::Solver::with_options(&enchants, options(4))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    let repairs = path
+        .steps
+        .iter()
+        .filter_map(|step| match step.kind {
+            solver::ResolvedStepKind::Repair(units) => Some(units),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // each unit repairs a quarter of the max durability and the repair adds an anvil use
+    assert_eq!(repairs, vec![3]);
+    assert_eq!(path.final_penalty, 15);
+
+    let without = solver::Solver::with_options(&enchants, options(0))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    assert!(without
+        .steps
+        .iter()
+        .all(|step| step.kind == solver::ResolvedStepKind::Combine));
+    assert!(path.cost >= without.cost + 3);
+
+    // with nothing to combine the repair is the only step, paying the prior work penalty
+    let alone = solver::Solver::with_options(&[], options(2))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    assert_eq!(alone.cost, 2 + 3);
+    assert_eq!(alone.steps.len(), 1);
+}
+
+#[test]
+fn material_repairs_take_as_many_anvil_uses_as_needed() {
+    let enchants = enchants(&[("Protection", 4), ("Unbreaking", 3)]);
+    let options = |damage, repair_materials| solver::SolverOptions {
+        item: solver::TargetItem {
+            damage,
+            ..Default::default()
+        },
+        max_durability: 100,
+        repair_materials,
+        ..Default::default()
+    };
+    let repairs = |path: &solver::ResolvedPath| {
+        path.steps
+            .iter()
+            .filter_map(|step| match step.kind {
+                solver::ResolvedStepKind::Repair(units) => Some(units),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // an anvil use takes at most 4 units, the rest needs another one
+    for (damage, repair_materials, expected) in [(150, 8, vec![4, 2]), (200, 5, vec![4, 1])] {
+        let path = solver::Solver::with_options(&enchants, options(damage, repair_materials))
+            .solve(&solver::CancellationToken::new(), |_| {})
+            .path
+            .unwrap();
+
+        let mut units = repairs(&path);
+        units.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(units, expected);
+    }
+
+    // materials alone don't make an undamaged item worth repairing
+    let undamaged = solver::Solver::with_options(&enchants, options(0, 4))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    let plain = solver::Solver::new(&enchants)
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    assert!(repairs(&undamaged).is_empty());
+    assert_eq!(undamaged.cost, plain.cost);
+
+    let alone = solver::Solver::with_options(&[], options(0, 4))
+        .solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(alone.status, solver::SolverStatus::NothingToCombine);
+
+    let full = solver::Solver::with_options(&enchants, options(150, 8))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    assert_eq!(full.damage, 0);
+}
+
+#[test]
+fn final_repairs_are_skipped_when_too_expensive() {
+    let enchants = enchants(&[("Unbreaking", 3)]);
+    let options = |damage, repair_materials| solver::SolverOptions {
+        item: solver::TargetItem {
+            anvil_use_count: 5,
+            damage,
+            ..Default::default()
+        },
+        max_durability: 1561,
+        repair_materials,
+        ..Default::default()
+    };
+
+    let undamaged = solver::Solver::with_options(&enchants, options(0, 0))
+        .solve(&solver::CancellationToken::new(), |_| {})
+        .path
+        .unwrap();
+    assert_eq!(undamaged.cost, 34);
+
+    // after the merge the penalty alone is 63, so the item stays damaged instead of the path failing
+    let damaged = solver::Solver::with_options(&enchants, options(500, 4))
+        .solve(&solver::CancellationToken::new(), |_| {});
+    assert_eq!(damaged.status, solver::SolverStatus::Solved);
+
+    let damaged = damaged.path.unwrap();
+    assert_eq!(damaged.damage, 500);
+    assert!(damaged
+        .steps
+        .iter()
+        .all(|step| !matches!(step.kind, solver::ResolvedStepKind::Repair(_))));
+}
+
+/// Combination in the brute force reference, only fresh books and the bare target are used
+#[derive(Clone)]
+struct Reference {
+    /// Levels paid for the enchants when it is sacrificed as a book
+    value: u32,
+    anvil_use_count: u32,
+    target: bool,
+}
+
+/// Cheapest total cost over every merge order without any pruning or deduplication,
+/// `None` if every order has a step reaching the cost limit
+///
+/// A rename is folded into a step on the target if any of them leaves room for the extra level,
+/// otherwise it is done on its own at the end
+fn brute_force(remaining: &[Reference], cost_limit: u32, rename: bool) -> Option<u32> {
+    brute_force_from(remaining, cost_limit, rename, false)
+}
+
+fn brute_force_from(
+    remaining: &[Reference],
+    cost_limit: u32,
+    rename: bool,
+    foldable: bool,
+) -> Option<u32> {
+    let penalty = |combination: &Reference| 2_u32.pow(combination.anvil_use_count) - 1;
+
+    if remaining.len() == 1 {
+        return match (rename, foldable) {
+            (false, _) => Some(0),
+            (true, true) => Some(1),
+            (true, false) => Some(1 + penalty(&remaining[0])).filter(|cost| *cost < cost_limit),
+        };
+    }
+
+    let mut best = None;
+    for left in 0..remaining.len() {
+        for right in 0..remaining.len() {
+            if left == right || remaining[right].target {
+                continue;
+            }
+
+            let (l, r) = (&remaining[left], &remaining[right]);
+            let cost = r.value + penalty(l) + penalty(r);
+            if cost >= cost_limit {
+                continue;
+            }
+
+            let mut next = remaining
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != left && *i != right)
+                .map(|(_, e)| e.clone())
+                .collect::<Vec<_>>();
+            next.push(Reference {
+                value: l.value + r.value,
+                anvil_use_count: u32::max(l.anvil_use_count, r.anvil_use_count) + 1,
+                target: l.target,
+            });
+
+            let foldable = foldable || (l.target && cost + 1 < cost_limit);
+            if let Some(rest) = brute_force_from(&next, cost_limit, rename, foldable) {
+                best = Some(best.map_or(cost + rest, |best: u32| best.min(cost + rest)));
+            }
+        }
+    }
+    best
+}
+
+#[test]
+fn search_matches_brute_force() {
+    let inputs: [&[(&str, u32)]; 4] = [
+        &[("Protection", 4), ("Unbreaking", 3), ("Mending", 1)],
+        &[
+            ("Protection", 4),
+            ("Unbreaking", 3),
+            ("Mending", 1),
+            ("Thorns", 3),
+        ],
+        &[
+            ("Sharpness", 5),
+            ("Looting", 3),
+            ("Fire Aspect", 2),
+            ("Unbreaking", 3),
+            ("Mending", 1),
+        ],
+        &[
+            ("Thorns", 3),
+            ("Curse of Binding", 1),
+            ("Curse of Vanishing", 1),
+            ("Feather Falling", 4),
+            ("Depth Strider", 3),
+        ],
+    ];
+
+    for input in inputs {
+        let enchants = enchants(input);
+        let reference = std::iter::once(Reference {
+            value: 0,
+            anvil_use_count: 0,
+            target: true,
+        })
+        .chain(enchants.iter().map(|e| Reference {
+            value: e.level * e.enchant.weight,
+            anvil_use_count: 0,
+            target: false,
+        }))
+        .collect::<Vec<_>>();
+
+        let result =
+            solver::Solver::new(&enchants).solve(&solver::CancellationToken::new(), |_| {});
+
+        assert_eq!(
+            result.path.map(|path| path.cost),
+            brute_force(&reference, solver::TOO_EXPENSIVE_COST, false),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn repeated_solves_pick_the_same_path() {
+    let enchants = enchants(&[
+        ("Protection", 4),
+        ("Unbreaking", 3),
+        ("Mending", 1),
+        ("Thorns", 3),
+        ("Feather Falling", 4),
+        ("Depth Strider", 3),
+    ]);
+
+    for (objective, pareto) in [
+        (solver::SolverObjective::Levels, false),
+        (solver::SolverObjective::MaxStep, false),
+        (solver::SolverObjective::Experience, false),
+        (solver::SolverObjective::Levels, true),
+    ] {
+        let solve = || {
+            let result = solver::Solver::with_options(
+                &enchants,
+                solver::SolverOptions {
+                    objective,
+                    pareto,
+                    ..Default::default()
+                },
+            )
+            .solve(&solver::CancellationToken::new(), |_| {});
+            format!("{:?} {:?}", result.path, result.alternatives)
+        };
+
+        let first = solve();
+        for _ in 0..4 {
+            assert_eq!(solve(), first);
+        }
+    }
+}
+
+#[test]
+fn rename_is_part_of_the_search() {
+    let inputs: [(u32, &[(&str, u32)]); 2] = [
+        (
+            3,
+            &[
+                ("Protection", 4),
+                ("Unbreaking", 3),
+                ("Mending", 1),
+                ("Thorns", 3),
+                ("Curse of Binding", 1),
+            ],
+        ),
+        (
+            4,
+            &[
+                ("Protection", 4),
+                ("Unbreaking", 3),
+                ("Mending", 1),
+                ("Thorns", 3),
+            ],
+        ),
+    ];
+
+    for (anvil_use_count, input) in inputs {
+        let enchants = enchants(input);
+        let reference = std::iter::once(Reference {
+            value: 0,
+            anvil_use_count,
+            target: true,
+        })
+        .chain(enchants.iter().map(|e| Reference {
+            value: e.level * e.enchant.weight,
+            anvil_use_count: 0,
+            target: false,
+        }))
+        .collect::<Vec<_>>();
+
+        // the cheapest path leaves no room on the target for the extra level, so renaming
+        // after it costs more than taking a path that does
+        let plain = brute_force(&reference, solver::TOO_EXPENSIVE_COST, false).unwrap();
+        let renamed = brute_force(&reference, solver::TOO_EXPENSIVE_COST, true).unwrap();
+        assert!(renamed > plain + 1);
+
+        let result = solver::Solver::with_options(
+            &enchants,
+            solver::SolverOptions {
+                item: solver::TargetItem {
+                    anvil_use_count,
+                    ..Default::default()
+                },
+                rename: Some("Chestplate".to_string()),
+                ..Default::default()
+            },
+        )
+        .solve(&solver::CancellationToken::new(), |_| {});
+
+        assert_eq!(
+            result.path.map(|path| path.cost),
+            Some(renamed),
+            "{:?}",
+            input
+        );
+    }
+}
@@ -1,35 +1,50 @@
-use std::{borrow::Borrow, collections::BTreeMap, sync::mpsc::Receiver, time::Duration};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 
 use eframe::{
     egui::{self, Button, RichText},
     epaint::{FontId, Stroke},
 };
-use egui::{Color32, Label, Layout, ScrollArea, Sense, Spinner, TextStyle, Ui, Vec2};
+use egui::{Color32, DragValue, Label, Layout, ScrollArea, Spinner, TextStyle, Ui, Vec2};
 use egui_extras::{Column, Size, StripBuilder, TableBuilder};
 use enchant_calc::{
     registry::{self, Item},
-    solver::{self, SolverResult},
+    solver::{self, ResolvedPath, ResolvedStepItem, ResolvedStepKind, SolverResult},
 };
 use wasm_timer::Instant;
 
 use crate::{
-    calculator::{self, CalculatorResponse},
+    calculator::{self, Calculation, CalculatorRequest, CalculatorResponse},
     step_ext::{ImageExt, StepExt},
 };
 
 pub struct App {
     selected_item: Item,
+    version: registry::Version,
+    custom_registries: Option<
+        &'static HashMap<(registry::Edition, registry::Version), registry::Registry<'static>>,
+    >,
     supported: bool,
-    calculator_receiver: Option<Receiver<CalculatorResponse>>,
+    calculation: Option<Calculation>,
     result: Option<SolverResult>,
+    /// Cheapest path found so far by the running calculation
+    best_so_far: Option<ResolvedPath>,
+    /// Index into `SolverResult::alternatives` shown in place of the best path
+    selected_alternative: Option<usize>,
     failed_result: Option<String>,
     working: bool,
     work_start: Instant,
     work_end: Instant,
-    paths_explored: u32,
+    paths_explored: u64,
     multiple_protection_types: bool,
+    solver_options: solver::SolverOptions,
     available_enchants: Vec<&'static registry::Enchant>,
     selected_levels: BTreeMap<&'static str, u32>,
+    existing_levels: BTreeMap<&'static str, u32>,
+    book_anvil_use_count: u32,
 }
 
 impl App {
@@ -55,25 +70,101 @@ impl App {
 
         Self {
             selected_item: Item::None,
+            version: registry::Version::LATEST,
+            custom_registries: None,
             result: None,
+            best_so_far: None,
+            selected_alternative: None,
             failed_result: None,
-            calculator_receiver: None,
+            calculation: None,
             working: false,
             supported,
             work_start: Instant::now(),
             work_end: Instant::now(),
             paths_explored: 0,
             multiple_protection_types: false,
+            solver_options: solver::SolverOptions::default(),
             available_enchants: Vec::new(),
             selected_levels: BTreeMap::new(),
+            existing_levels: BTreeMap::new(),
+            book_anvil_use_count: 0,
         }
     }
 
-    fn item_changed(&mut self) {
+    /// Uses a user supplied registry in place of the built-in one
+    pub fn with_registry(mut self, registry: registry::Registry<'static>) -> Self {
+        // leaking because the enchant list holds static references into the registry
+        self.custom_registries = Some(Box::leak(Box::new(registry.variants())));
+        self
+    }
+
+    fn registry(&self) -> &'static registry::Registry<'static> {
+        match self.custom_registries {
+            Some(registries) => &registries[&(self.solver_options.edition, self.version)],
+            None => registry::registry(self.solver_options.edition, self.version),
+        }
+    }
+
+    /// Stops the running calculation, its result would be stale
+    fn cancel_calculation(&mut self) {
+        if let Some(ref calculation) = self.calculation {
+            calculation.cancel();
+        }
+    }
+
+    /// Settings going into a `CalculatorRequest` besides the selected enchants and the inventory
+    fn request_settings(&self) -> impl PartialEq {
+        let options = &self.solver_options;
+        (
+            (
+                options.edition,
+                self.version,
+                self.multiple_protection_types,
+            ),
+            (options.objective, options.strategy, options.pareto),
+            (
+                options.cost_limit,
+                options.time_limit,
+                options.rename.clone(),
+            ),
+            (
+                options.item.anvil_use_count,
+                options.item.damage,
+                options.max_durability,
+                options.repair_materials,
+            ),
+        )
+    }
+
+    /// Drops the running calculation and the shown result, neither matches the inputs anymore
+    ///
+    /// Responses the calculation sends before noticing the cancellation are dropped with it
+    fn inputs_changed(&mut self) {
+        self.cancel_calculation();
+        self.calculation = None;
+        self.working = false;
+        self.best_so_far = None;
+        self.paths_explored = 0;
+
         self.result = None;
+        self.selected_alternative = None;
+        self.failed_result = None;
+    }
+
+    fn item_changed(&mut self) {
+        self.inputs_changed();
+
+        if self.selected_item.since() > self.version {
+            self.selected_item = Item::None;
+        }
+
         self.selected_levels.clear();
+        self.existing_levels.clear();
+        self.solver_options.books.clear();
+        self.solver_options.sacrifices.clear();
 
-        let mut enchants = registry::REGISTRY
+        let mut enchants = self
+            .registry()
             .enchants
             .values()
             .filter(|enchant| enchant.items.contains(&self.selected_item))
@@ -105,7 +196,7 @@ impl App {
                 enchant
                     .incompatible
                     .iter()
-                    .filter_map(|e| registry::REGISTRY.enchants.get(e.as_str()))
+                    .filter_map(|e| self.registry().enchants.values().find(|o| o.name == *e))
                     .filter(|e| e.items.contains(&self.selected_item)),
             );
 
@@ -118,6 +209,11 @@ impl App {
     }
 
     fn enchant_selection(&mut self, ui: &mut Ui) {
+        let old_selected_levels = self.selected_levels.clone();
+        let old_existing_levels = self.existing_levels.clone();
+        let old_settings = self.request_settings();
+        let mut inventory_changed = false;
+
         let old_spacing = ui.style().spacing.item_spacing;
         let new_spacing = Vec2::new(16.0, old_spacing.y);
         ui.style_mut().spacing.item_spacing = new_spacing;
@@ -127,6 +223,7 @@ impl App {
                 .striped(true)
                 .column(Column::initial(150.0).at_least(150.0))
                 .column(Column::initial(100.0).at_least(100.0))
+                .column(Column::initial(60.0).at_least(60.0))
                 .resizable(false)
                 .header(32.0, |mut header| {
                     header.col(|ui| {
@@ -136,6 +233,10 @@ impl App {
                     header.col(|ui| {
                         ui.strong("Level");
                     });
+
+                    header.col(|ui| {
+                        ui.strong("On item");
+                    });
                 })
                 .body(|mut body| {
                     for available_enchant in &self.available_enchants {
@@ -188,14 +289,289 @@ impl App {
                                     }
                                 });
                             });
+
+                            row.col(|ui| {
+                                ui.horizontal_centered(|ui| {
+                                    let mut existing_level = self
+                                        .existing_levels
+                                        .get(available_enchant.name.as_str())
+                                        .copied()
+                                        .unwrap_or(0);
+
+                                    if ui
+                                        .add(
+                                            DragValue::new(&mut existing_level)
+                                                .clamp_range(0..=available_enchant.level_max),
+                                        )
+                                        .changed()
+                                    {
+                                        match existing_level {
+                                            0 => self
+                                                .existing_levels
+                                                .remove(available_enchant.name.as_str()),
+                                            _ => self
+                                                .existing_levels
+                                                .insert(&available_enchant.name, existing_level),
+                                        };
+                                    }
+                                });
+                            });
                         });
                     }
                 });
+
+            inventory_changed = self.inventory_books(ui);
+
+            ui.horizontal(|ui| {
+                ui.label("Prior work (anvil uses)");
+                ui.add(
+                    DragValue::new(&mut self.solver_options.item.anvil_use_count)
+                        .clamp_range(0..=10),
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Damage");
+                ui.add(
+                    DragValue::new(&mut self.solver_options.item.damage)
+                        .clamp_range(0..=self.solver_options.max_durability),
+                );
+                ui.label("of");
+                ui.add(DragValue::new(&mut self.solver_options.max_durability));
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Repair materials");
+                ui.add(DragValue::new(&mut self.solver_options.repair_materials));
+                ui.label("units");
+            });
+
+            let old_edition = self.solver_options.edition;
+            let old_version = self.version;
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("edition_picker")
+                    .selected_text(format!("{}", self.solver_options.edition))
+                    .show_ui(ui, |ui| {
+                        for variant in [registry::Edition::Java, registry::Edition::Bedrock] {
+                            ui.selectable_value(
+                                &mut self.solver_options.edition,
+                                variant,
+                                format!("{variant}"),
+                            );
+                        }
+                    });
+
+                egui::ComboBox::from_id_source("version_picker")
+                    .selected_text(format!("{}", self.version))
+                    .show_ui(ui, |ui| {
+                        for variant in registry::Version::ALL {
+                            ui.selectable_value(&mut self.version, variant, format!("{variant}"));
+                        }
+                    });
+            });
+            if old_edition != self.solver_options.edition || old_version != self.version {
+                self.item_changed();
+            }
+
             ui.checkbox(
                 &mut self.multiple_protection_types,
                 "Minecraft 1.14.1 (multiple protection types)",
             );
+
+            let mut survival = self.solver_options.cost_limit.is_some();
+            if ui
+                .checkbox(&mut survival, "Survival (\"Too Expensive!\" limit)")
+                .changed()
+            {
+                self.solver_options.cost_limit = survival.then_some(solver::TOO_EXPENSIVE_COST);
+            }
+
+            if let Some(ref mut cost_limit) = self.solver_options.cost_limit {
+                ui.horizontal(|ui| {
+                    ui.label("Too expensive at");
+                    ui.add(DragValue::new(cost_limit).clamp_range(1..=u32::MAX));
+                    ui.label("levels");
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Minimize");
+                egui::ComboBox::from_id_source("objective_picker")
+                    .selected_text(format!("{}", self.solver_options.objective))
+                    .show_ui(ui, |ui| {
+                        for variant in [
+                            solver::SolverObjective::Levels,
+                            solver::SolverObjective::Experience,
+                            solver::SolverObjective::MaxStep,
+                            solver::SolverObjective::PriorWork,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.solver_options.objective,
+                                variant,
+                                format!("{variant}"),
+                            );
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Search");
+                egui::ComboBox::from_id_source("strategy_picker")
+                    .selected_text(format!("{}", self.solver_options.strategy))
+                    .show_ui(ui, |ui| {
+                        for variant in [
+                            solver::SolverStrategy::Exact,
+                            solver::SolverStrategy::Greedy,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.solver_options.strategy,
+                                variant,
+                                format!("{variant}"),
+                            );
+                        }
+                    });
+            });
+
+            ui.checkbox(
+                &mut self.solver_options.pareto,
+                "Show alternatives (cost, most expensive step and prior work)",
+            );
+
+            let mut renamed = self.solver_options.rename.is_some();
+            if ui.checkbox(&mut renamed, "Rename").changed() {
+                self.solver_options.rename = renamed.then(String::new);
+            }
+
+            if let Some(ref mut name) = self.solver_options.rename {
+                ui.horizontal(|ui| {
+                    ui.label("Rename to");
+                    ui.text_edit_singleline(name);
+                });
+            }
+
+            let mut time_limited = self.solver_options.time_limit.is_some();
+            if ui.checkbox(&mut time_limited, "Time limit").changed() {
+                self.solver_options.time_limit = time_limited.then_some(Duration::from_secs(10));
+            }
+
+            if let Some(ref mut time_limit) = self.solver_options.time_limit {
+                let mut seconds = time_limit.as_secs();
+                ui.horizontal(|ui| {
+                    ui.label("Stop searching after");
+                    ui.add(DragValue::new(&mut seconds).clamp_range(1..=3600));
+                    ui.label("seconds");
+                });
+                *time_limit = Duration::from_secs(seconds);
+            }
+        });
+
+        if old_selected_levels != self.selected_levels
+            || old_existing_levels != self.existing_levels
+            || old_settings != self.request_settings()
+            || inventory_changed
+        {
+            self.inputs_changed();
+        }
+    }
+
+    /// Returns true if a book or item was added, removed or had its damage changed
+    fn inventory_books(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            let enabled = !self.selected_levels.is_empty();
+            let add_book_button = ui.add_enabled(enabled, Button::new("Add as book"));
+            let add_item_button = ui.add_enabled(enabled, Button::new("Add as item"));
+
+            if add_book_button.clicked() || add_item_button.clicked() {
+                let enchants = self
+                    .selected_levels
+                    .iter()
+                    .map(|(name, level)| {
+                        solver::Enchant::new(self.registry().enchants[*name].clone(), *level)
+                    })
+                    .collect::<Vec<_>>();
+
+                match add_book_button.clicked() {
+                    true => self.solver_options.books.push(solver::InventoryBook {
+                        enchants,
+                        anvil_use_count: self.book_anvil_use_count,
+                    }),
+                    false => self.solver_options.sacrifices.push(solver::TargetItem {
+                        anvil_use_count: self.book_anvil_use_count,
+                        enchants,
+                        damage: 0,
+                    }),
+                }
+                self.selected_levels.clear();
+                changed = true;
+            }
+
+            ui.label("with prior work");
+            ui.add(DragValue::new(&mut self.book_anvil_use_count).clamp_range(0..=10));
         });
+
+        let mut removed_book = None;
+        for (index, book) in self.solver_options.books.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").clicked() {
+                    removed_book = Some(index);
+                }
+
+                let enchants = book
+                    .enchants
+                    .iter()
+                    .map(|e| ResolvedStepItem::Enchant(e.clone()).format(self.selected_item))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                ui.label(format!(
+                    "Book: {} (prior work {})",
+                    enchants, book.anvil_use_count
+                ));
+            });
+        }
+
+        if let Some(index) = removed_book {
+            self.solver_options.books.remove(index);
+            changed = true;
+        }
+
+        let mut removed_sacrifice = None;
+        let max_durability = self.solver_options.max_durability;
+        for (index, sacrifice) in self.solver_options.sacrifices.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").clicked() {
+                    removed_sacrifice = Some(index);
+                }
+
+                let enchants = sacrifice
+                    .enchants
+                    .iter()
+                    .map(|e| ResolvedStepItem::Enchant(e.clone()).format(self.selected_item))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                ui.label(format!(
+                    "{}: {} (prior work {})",
+                    ResolvedStepItem::Sacrifice(index as u32).format(self.selected_item),
+                    enchants,
+                    sacrifice.anvil_use_count
+                ));
+
+                ui.label("damage");
+                changed |= ui
+                    .add(DragValue::new(&mut sacrifice.damage).clamp_range(0..=max_durability))
+                    .changed();
+            });
+        }
+
+        if let Some(index) = removed_sacrifice {
+            self.solver_options.sacrifices.remove(index);
+            changed = true;
+        }
+
+        changed
     }
 
     fn show_steps(&mut self, ui: &mut Ui) {
@@ -203,33 +579,171 @@ impl App {
             return;
         };
 
-        let Some(ref path) = result.path else {
-            ui.label(RichText::new("No solution found").strong().heading());
+        let Some(ref best_path) = result.path else {
+            let reason = match result.status {
+                solver::SolverStatus::TooExpensive => {
+                    "No solution found, every path has a step that is too expensive"
+                }
+                solver::SolverStatus::Incompatible => {
+                    "No solution found, some of the enchants are incompatible"
+                }
+                _ => "No solution found",
+            };
+            ui.label(RichText::new(reason).strong().heading());
             return;
         };
 
+        let mut selected_alternative = self.selected_alternative;
+        if result.alternatives.len() > 1 {
+            let describe = |path: &ResolvedPath| {
+                format!(
+                    "{} levels, most expensive step {}, prior work penalty {}",
+                    path.cost, path.max_step_cost, path.final_penalty
+                )
+            };
+
+            ui.horizontal(|ui| {
+                ui.label("Alternative");
+                egui::ComboBox::from_id_source("alternative_picker")
+                    .selected_text(match selected_alternative {
+                        Some(index) => describe(&result.alternatives[index]),
+                        None => format!("Best by {}", best_path.objective),
+                    })
+                    .wrap(true)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut selected_alternative,
+                            None,
+                            format!("Best by {}", best_path.objective),
+                        );
+                        for (index, alternative) in result.alternatives.iter().enumerate() {
+                            ui.selectable_value(
+                                &mut selected_alternative,
+                                Some(index),
+                                describe(alternative),
+                            );
+                        }
+                    });
+            });
+        }
+        self.selected_alternative = selected_alternative;
+
+        let path = selected_alternative
+            .and_then(|index| result.alternatives.get(index))
+            .unwrap_or(best_path);
+
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
                 ui.label(RichText::new("Total cost:").strong().heading());
                 ui.label(RichText::new(format!("{} levels", path.cost)).heading());
             });
 
+            ui.label(format!(
+                "{} experience points, most expensive step {} levels, prior work penalty {}",
+                path.experience, path.max_step_cost, path.final_penalty
+            ));
+
+            if path.damage > 0 {
+                ui.label(format!(
+                    "{} durability is left unrepaired, repairing further is out of materials or too expensive",
+                    path.damage
+                ));
+            }
+
             ui.label(format!(
                 "Completed in {}ms",
                 (self.work_end - self.work_start).as_millis()
-            ))
+            ));
+
+            let stats = &result.stats;
+            ui.label(format!(
+                "Searched for {}ms: tried {} steps, expanded {} paths, reached {} complete paths, \
+                 pruned {} and skipped {} duplicates",
+                stats.elapsed.as_millis(),
+                stats.paths_tried,
+                stats.nodes_expanded,
+                stats.leaves,
+                stats.pruned,
+                stats.deduplicated
+            ));
+
+            if result.status == solver::SolverStatus::OutOfTime {
+                ui.label("Time limit reached, a cheaper path might exist");
+            }
+
+            if path.score() > result.lower_bound {
+                ui.label(format!(
+                    "{} is at most {} above the best path",
+                    path.objective,
+                    path.score() - result.lower_bound
+                ));
+            }
         });
 
+        self.show_path_steps(ui, path);
+    }
+
+    fn show_path_steps(&self, ui: &mut Ui, path: &ResolvedPath) {
         ui.add_space(25.0);
 
         ui.label(RichText::new("Steps").strong().heading());
 
         ui.separator();
 
+        let name = self.solver_options.rename.as_deref().unwrap_or_default();
+
         for (index, step) in path.steps.iter().enumerate() {
             ui.horizontal_wrapped(|ui| {
                 ui.style_mut().spacing.item_spacing.x = 1.0;
 
+                let action = match step.kind {
+                    ResolvedStepKind::Combine => None,
+                    ResolvedStepKind::Repair(units) => Some((
+                        "Repair",
+                        format!(" with {} units of repair material", units),
+                    )),
+                    ResolvedStepKind::Rename => Some(("Rename", format!(" to \"{}\"", name))),
+                };
+
+                if let Some((verb, details)) = action {
+                    ui.add(
+                        Label::new(
+                            RichText::new(format!("{}. {} ", index + 1, verb))
+                                .text_style(TextStyle::Name("step".into())),
+                        )
+                        .wrap(true),
+                    );
+
+                    step.left
+                        .get_image(self.selected_item)
+                        .show_max_size(ui, Vec2::new(24.0, 24.0));
+
+                    ui.add(
+                        Label::new(
+                            RichText::new(format!(
+                                " {}{}",
+                                step.left.format(self.selected_item),
+                                details
+                            ))
+                            .text_style(TextStyle::Name("step".into())),
+                        )
+                        .wrap(true),
+                    );
+
+                    if step.renamed && step.kind != ResolvedStepKind::Rename {
+                        ui.add(
+                            Label::new(
+                                RichText::new(format!(" and rename it to \"{}\"", name))
+                                    .text_style(TextStyle::Name("step".into())),
+                            )
+                            .wrap(true),
+                        );
+                    }
+
+                    ui.add_space(50.0);
+                    return;
+                }
+
                 ui.add(
                     Label::new(
                         RichText::new(format!("{}. Combine ", index + 1))
@@ -262,6 +776,51 @@ impl App {
                     .wrap(true),
                 );
 
+                if !step.upgraded.is_empty() {
+                    let upgraded = step
+                        .upgraded
+                        .iter()
+                        .map(|e| ResolvedStepItem::Enchant(e.clone()).format(self.selected_item))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    ui.add(
+                        Label::new(
+                            RichText::new(format!(" (upgrades to {})", upgraded))
+                                .text_style(TextStyle::Name("step".into())),
+                        )
+                        .wrap(true),
+                    );
+                }
+
+                if step.renamed {
+                    ui.add(
+                        Label::new(
+                            RichText::new(format!(" and rename it to \"{}\"", name))
+                                .text_style(TextStyle::Name("step".into())),
+                        )
+                        .wrap(true),
+                    );
+                }
+
+                if !step.enchants.is_empty() {
+                    let enchants = step
+                        .enchants
+                        .iter()
+                        .map(|e| ResolvedStepItem::Enchant(e.clone()).format(self.selected_item))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    ui.add(
+                        Label::new(
+                            RichText::new(format!(", resulting in {}", enchants))
+                                .text_style(TextStyle::Name("step".into()))
+                                .weak(),
+                        )
+                        .wrap(true),
+                    );
+                }
+
                 ui.add_space(50.0);
             });
         }
@@ -280,6 +839,15 @@ impl App {
                     .heading(),
                 );
             });
+
+            if let Some(ref path) = self.best_so_far {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Best so far:").strong().heading());
+                    ui.label(RichText::new(format!("{} levels", path.cost)).heading());
+                });
+
+                self.show_path_steps(ui, path);
+            }
         } else if let Some(ref failed_result) = self.failed_result {
             ui.label(
                 RichText::new(format!(
@@ -289,6 +857,20 @@ impl App {
                 .strong()
                 .heading(),
             );
+        } else if let (None, Some(ref path)) = (&self.result, &self.best_so_far) {
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Best so far:").strong().heading());
+                    ui.label(RichText::new(format!("{} levels", path.cost)).heading());
+                });
+
+                ui.label(format!(
+                    "Stopped early after {}ms, a cheaper path might exist",
+                    (self.work_end - self.work_start).as_millis()
+                ));
+            });
+
+            self.show_path_steps(ui, path);
         } else {
             ui.vertical(|ui| {
                 self.show_steps(ui);
@@ -345,6 +927,7 @@ impl eframe::App for App {
                                             .selected_text(format!("{}", self.selected_item))
                                             .wrap(true)
                                             .show_ui(ui, |ui| {
+                                                let version = self.version;
                                                 for variant in [
                                                     Item::Helmet,
                                                     Item::Chestplate,
@@ -363,13 +946,17 @@ impl eframe::App for App {
                                                     Item::Crossbow,
                                                     Item::FishingRod,
                                                     Item::Shears,
-                                                    Item::ShearsBedrock,
                                                     Item::FlintAndSteel,
                                                     Item::Compass,
                                                     Item::CarrotOnAStick,
                                                     Item::Pumpkin,
                                                     Item::WarpedFungusOnAStick,
-                                                ] {
+                                                    Item::Brush,
+                                                    Item::Mace,
+                                                ]
+                                                .into_iter()
+                                                .filter(|e| e.since() <= version)
+                                                {
                                                     if ui
                                                         .selectable_value(
                                                             &mut self.selected_item,
@@ -386,32 +973,66 @@ impl eframe::App for App {
                                 });
                                 strip.cell(|ui| {
                                     ui.vertical_centered_justified(|ui| {
-                                        let button = match self.working {
-                                            true => Button::new("Calculate").sense(Sense::hover()),
-                                            false => Button::new("Calculate"),
-                                        };
-
-                                        if ui.add(button).clicked() {
-                                            let enchants = self
+                                        if self.working {
+                                            if ui.button("Stop").clicked() {
+                                                self.cancel_calculation();
+                                            }
+                                        } else if ui.button("Calculate").clicked() {
+                                            let mut enchants = self
                                                 .selected_levels
                                                 .iter()
                                                 .map(|(name, level)| {
                                                     solver::Enchant::new(
-                                                        registry::REGISTRY.enchants[name].clone(),
+                                                        self.registry().enchants[*name].clone(),
+                                                        *level,
+                                                    )
+                                                })
+                                                .collect::<Vec<_>>();
+
+                                            let mut options = self.solver_options.clone();
+                                            options.item.enchants = self
+                                                .existing_levels
+                                                .iter()
+                                                .map(|(name, level)| {
+                                                    solver::Enchant::new(
+                                                        self.registry().enchants[*name].clone(),
                                                         *level,
                                                     )
                                                 })
                                                 .collect::<Vec<_>>();
 
+                                            // 1.14.1 lets the protection types stack, so the
+                                            // selection is combined as if they were compatible
+                                            if self.multiple_protection_types {
+                                                let names = enchants
+                                                    .iter()
+                                                    .chain(options.item.enchants.iter())
+                                                    .map(|e| e.enchant.name.clone())
+                                                    .collect::<Vec<_>>();
+                                                for enchant in enchants
+                                                    .iter_mut()
+                                                    .chain(options.item.enchants.iter_mut())
+                                                {
+                                                    enchant
+                                                        .enchant
+                                                        .incompatible
+                                                        .retain(|e| !names.contains(e));
+                                                }
+                                            }
+
                                             self.failed_result = None;
+                                            self.best_so_far = None;
                                             self.working = true;
                                             self.work_start = Instant::now();
 
-                                            let result = calculator::calculate(enchants);
+                                            let result = calculator::calculate(CalculatorRequest {
+                                                enchants,
+                                                options,
+                                            });
 
                                             match result {
-                                                Ok(receiver) => {
-                                                    self.calculator_receiver = Some(receiver)
+                                                Ok(calculation) => {
+                                                    self.calculation = Some(calculation)
                                                 }
                                                 Err(e) => {
                                                     tracing::error!(
@@ -450,14 +1071,27 @@ impl eframe::App for App {
                 });
         });
 
-        if let Some(ref calculator_receiver) = self.calculator_receiver {
-            while let Ok(received) = calculator_receiver.try_recv() {
+        if let Some(ref calculation) = self.calculation {
+            while let Ok(received) = calculation.try_recv() {
                 match received {
                     CalculatorResponse::Progress(paths_tried) => {
-                        self.paths_explored += paths_tried;
+                        self.paths_explored += paths_tried as u64;
+                    }
+                    CalculatorResponse::Improved(path) => {
+                        self.best_so_far = Some(path);
                     }
                     CalculatorResponse::Done(result) => {
                         self.result = Some(result);
+                        self.selected_alternative = None;
+                        self.best_so_far = None;
+                        self.paths_explored = 0;
+                        self.work_end = Instant::now();
+                        self.working = false;
+                    }
+                    // the best path found until the stop stays on screen in place of a result
+                    CalculatorResponse::Cancelled => {
+                        self.result = None;
+                        self.selected_alternative = None;
                         self.paths_explored = 0;
                         self.work_end = Instant::now();
                         self.working = false;
@@ -470,7 +1104,7 @@ impl eframe::App for App {
         }
 
         if !self.working {
-            self.calculator_receiver = None;
+            self.calculation = None;
         }
         ctx.request_repaint_after(Duration::from_secs(1));
     }
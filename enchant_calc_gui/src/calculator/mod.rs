@@ -1,20 +1,59 @@
 #![allow(non_upper_case_globals)]
 
 use self::error::CalculatorError;
-use enchant_calc::solver::{self, SolverResult};
+use enchant_calc::solver::{self, ResolvedPath, SolverProgress, SolverResult};
 use serde::{Deserialize, Serialize};
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, TryRecvError};
 
 pub mod error;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculatorRequest {
+    pub enchants: Vec<solver::Enchant>,
+    pub options: solver::SolverOptions,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CalculatorResponse {
     Progress(u32),
+    /// A cheaper path was found, the search continues
+    Improved(ResolvedPath),
     Done(SolverResult),
+    Cancelled,
     Failed(String),
 }
 
-#[cfg(target = "wasm32")]
+/// A running calculation
+pub struct Calculation {
+    receiver: Receiver<CalculatorResponse>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cancellation: solver::CancellationToken,
+    #[cfg(target_arch = "wasm32")]
+    worker: web_sys::Worker,
+    #[cfg(target_arch = "wasm32")]
+    sender: std::sync::mpsc::Sender<CalculatorResponse>,
+}
+
+impl Calculation {
+    pub fn try_recv(&self) -> Result<CalculatorResponse, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Stops the calculation, a `CalculatorResponse::Cancelled` is received once it stopped
+    pub fn cancel(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.cancellation.cancel();
+
+        // the worker doesn't share memory with us, so it can only be stopped from the outside
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.worker.terminate();
+            let _ = self.sender.send(CalculatorResponse::Cancelled);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
 pub fn spawn_future<F>(future: F)
 where
     F: std::future::Future<Output = ()> + 'static,
@@ -22,22 +61,34 @@ where
     wasm_bindgen_futures::spawn_local(future);
 }
 
-fn run_solver(enchants: Vec<solver::Enchant>, progress: impl FnMut(u32)) -> SolverResult {
-    let solver = solver::Solver::new(&enchants);
-    solver.solve(progress)
+fn run_solver(
+    request: CalculatorRequest,
+    cancellation: &solver::CancellationToken,
+    progress: impl FnMut(SolverProgress) + Send,
+) -> SolverResult {
+    let solver = solver::Solver::with_options(&request.enchants, request.options);
+    solver.solve(cancellation, progress)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn calculate(
-    enchants: Vec<solver::Enchant>,
-) -> Result<Receiver<CalculatorResponse>, CalculatorError> {
+pub fn calculate(request: CalculatorRequest) -> Result<Calculation, CalculatorError> {
     let (tx, rx) = std::sync::mpsc::channel();
+    let cancellation = solver::CancellationToken::new();
 
+    let thread_cancellation = cancellation.clone();
     std::thread::spawn(move || {
         let mut last_update = std::time::Instant::now();
         let mut tried_since_last_update = 0;
 
-        let result = run_solver(enchants, |paths_tried| {
+        let result = run_solver(request, &thread_cancellation, |progress| {
+            let paths_tried = match progress {
+                SolverProgress::PathsTried(paths_tried) => paths_tried,
+                SolverProgress::Improved(path) => {
+                    let _ = tx.send(CalculatorResponse::Improved(path));
+                    return;
+                }
+            };
+
             // buffering updates to not create a lot of thread communication slowing down the application
             tried_since_last_update += paths_tried;
             if (std::time::Instant::now() - last_update).as_secs() >= 1 {
@@ -47,10 +98,18 @@ pub fn calculate(
                 last_update = std::time::Instant::now();
             }
         });
-        let _ = tx.send(CalculatorResponse::Done(result));
+
+        let response = match result.status {
+            solver::SolverStatus::Cancelled => CalculatorResponse::Cancelled,
+            _ => CalculatorResponse::Done(result),
+        };
+        let _ = tx.send(response);
     });
 
-    Ok(rx)
+    Ok(Calculation {
+        receiver: rx,
+        cancellation,
+    })
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -88,11 +147,26 @@ pub fn calculate_worker(message: js_sys::Uint8Array) {
     console_error_panic_hook::set_once();
 
     let calculate = move || -> Result<(), CalculatorError> {
-        let enchants: Vec<solver::Enchant> = decode(message)?;
+        let request: CalculatorRequest = decode(message)?;
 
         let mut last_update = performance.now();
         let mut tried_since_last_update = 0;
-        let result = run_solver(enchants, |paths_tried| {
+        // cancelling terminates the whole worker, so the token is never used here
+        let cancellation = solver::CancellationToken::new();
+        let result = run_solver(request, &cancellation, |progress| {
+            let paths_tried = match progress {
+                SolverProgress::PathsTried(paths_tried) => paths_tried,
+                SolverProgress::Improved(path) => {
+                    let encoded = encode(&CalculatorResponse::Improved(path)).unwrap();
+                    js_sys::global()
+                        .dyn_into::<web_sys::DedicatedWorkerGlobalScope>()
+                        .unwrap()
+                        .post_message(&encoded.into())
+                        .unwrap();
+                    return;
+                }
+            };
+
             // buffering updates to not create a lot of thread communication slowing down the application
             tried_since_last_update += paths_tried;
 
@@ -131,14 +205,13 @@ pub fn calculate_worker(message: js_sys::Uint8Array) {
 }
 
 #[cfg(target_arch = "wasm32")]
-pub fn calculate(
-    enchants: Vec<solver::Enchant>,
-) -> Result<Receiver<CalculatorResponse>, error::CalculatorError> {
+pub fn calculate(request: CalculatorRequest) -> Result<Calculation, error::CalculatorError> {
     use js_sys::Uint8Array;
 
     let (tx, rx) = std::sync::mpsc::channel();
+    let sender = tx.clone();
 
-    let encoded: JsValue = encode(&enchants)?.into();
+    let encoded: JsValue = encode(&request)?.into();
 
     let worker = web_sys::Worker::new_with_options(
         "./worker.mjs",
@@ -158,5 +231,9 @@ pub fn calculate(
     // forgetting because we don't need the closure destroyed before worker finishes
     std::mem::forget(callback);
 
-    Ok(rx)
+    Ok(Calculation {
+        receiver: rx,
+        worker,
+        sender,
+    })
 }
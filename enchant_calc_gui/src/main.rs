@@ -2,13 +2,37 @@
 fn main() -> Result<(), eframe::Error> {
     tracing_subscriber::fmt::init();
 
+    // optional path to a custom registry in json or toml format
+    let registry = match std::env::args().nth(1) {
+        Some(path) => match enchant_calc::registry::Registry::from_path(&path) {
+            Ok(registry) => {
+                let report = registry.validate();
+                if !report.is_valid() {
+                    tracing::warn!("Registry {} has inconsistencies:\n{}", path, report);
+                }
+                Some(registry)
+            }
+            Err(e) => {
+                tracing::error!("Failed to load registry {}! {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
     eframe::run_native(
         "Enchantment Calculator",
         eframe::NativeOptions {
             follow_system_theme: true,
             ..Default::default()
         },
-        Box::new(|cc| Box::new(enchant_calc_gui::App::new(cc, true))),
+        Box::new(|cc| {
+            let app = enchant_calc_gui::App::new(cc, true);
+            Box::new(match registry {
+                Some(registry) => app.with_registry(registry),
+                None => app,
+            })
+        }),
     )
 }
 
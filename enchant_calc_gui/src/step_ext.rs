@@ -12,6 +12,7 @@ impl StepExt for ResolvedStepItem {
     fn format(&self, item: registry::Item) -> String {
         match self {
             ResolvedStepItem::Item => item.to_string(),
+            ResolvedStepItem::Sacrifice(index) => format!("{} (sacrifice {})", item, index + 1),
             ResolvedStepItem::Enchant(e) => match e.level > 1 {
                 true => format!("{} {}", e.enchant.name, e.level),
                 false => e.enchant.name.to_string(),
@@ -43,7 +44,7 @@ pub trait ImageExt {
 impl ImageExt for ResolvedStepItem {
     fn get_image(&self, item: registry::Item) -> &RetainedImage {
         match self {
-            ResolvedStepItem::Item => match item {
+            ResolvedStepItem::Item | ResolvedStepItem::Sacrifice(_) => match item {
                 registry::Item::None => &images::ENCHANTED_BOOK,
                 registry::Item::Helmet => &images::HELMET,
                 registry::Item::Chestplate => &images::CHESTPLATE,
@@ -68,6 +69,8 @@ impl ImageExt for ResolvedStepItem {
                 registry::Item::CarrotOnAStick => &images::CARROT_ON_A_STICK,
                 registry::Item::Pumpkin => &images::PUMPKIN,
                 registry::Item::WarpedFungusOnAStick => &images::WARPED_FUNGUS_ON_A_STICK,
+                // no textures for these yet
+                registry::Item::Brush | registry::Item::Mace => &images::ENCHANTED_BOOK,
             },
             ResolvedStepItem::Enchant(_) => &images::ENCHANTED_BOOK,
         }